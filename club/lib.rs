@@ -1,60 +1,161 @@
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
-pub use self::club::ClubRef;
+pub use self::club::{ClubRef, ClubError, Categoria, ActividadesIncluidas, EstadoSocio, Rol, CambioConfiguracion, ValorProgramado, EstadoCuenta, GrupoFamiliar, ResumenPago};
 
 #[ink::contract]
 mod club{
-  use core::panic;
   use ink::storage::Mapping;
+  use ink::storage::Lazy;
+  use ink::storage::traits::StorageKey;
+  use ink::env::hash::{Blake2x128, HashOutput};
   use ink::prelude::vec::Vec;
   use ink::prelude::string::String;
   use ink_prelude::string::ToString;
 
+  ///Version del formato en que se guardan los socios y los pagos, se incrementa cada vez que cambian sus campos o como se identifican
+  ///-1: los socios y los pagos sin los campos de estado, recargos, pagos parciales y planes de cuotas
+  ///-2: los socios identificados por su dni
+  ///-3: los socios identificados por el hash de su dni con la sal secreta del club
+  const VERSION_STORAGE: u32 = 3;
+  ///Cantidad de registros que recorre cada llamada a migrar si el owner no cambio el limite
+  const LIMITE_MIGRACION: u32 = 50;
+  ///Duracion de cada periodo de facturacion, 30 dias
+  const PERIODO_FACTURACION: u64 = 30 * 24 * 60 * 60;
+  ///Dias que tiene la direccion propuesta como nuevo owner para aceptar
+  const DIAS_PROPUESTA_OWNER: u64 = 7;
+  ///Dias que tienen los aprobadores para aprobar una propuesta de cambio de configuracion
+  const DIAS_PROPUESTA_CAMBIO: u64 = 7;
+
   ///En el struct de Club se va a guardar:
-  ///-Informacion de todos los socios que se registren en el club
-  ///-Los pagos de cada socio registrado
-  ///-Precio de las tres categorias
-  ///-El owner guarda la direccion del duenio del contrato
-  ///-El vector de direcciones guarda todas las direcciones autorizadas para realizar operaciones
-  ///-La politica nos indica que si esta desactivada, cualquiera podra realizar operaciones, y si esta activada, solo las direcciones autorizadas podran hacerlo
+  ///-Informacion de todos los socios que se registren en el club, guardados por su id. Para no publicar los dni, el id de un socio
+  /// es el hash Blake2 de 128 bits de una sal secreta del club seguida del dni (ver calcular_id_socio), y todas las operaciones reciben ese id.
+  /// La sal no se guarda en el contrato ni se manda en ninguna transaccion: como los dni tienen unos 10^8 valores posibles,
+  /// quien conozca la sal puede armar la tabla de dni a id probandolos todos, asi que los ids solo protegen los dni mientras la sal se mantenga secreta
+  ///-Los ids que cargo el owner para pasar a guardar por id a los socios que estaban guardados por dni, hasta que se migran
+  ///-El orden en que se registraron los socios (posicion -> id) y la cantidad de socios
+  ///-Los pagos de cada socio registrado, guardados por id, y la cantidad de pagos
+  ///-Los ids de los pagos de cada socio, para no tener que recorrer todos los pagos del club
+  ///-La cuenta vinculada a cada socio y el socio de cada cuenta, y los vinculos que las cuentas pidieron y todavia no se aprobaron
+  ///-El catalogo de categorias, guardadas por id, y la cantidad de categorias creadas
+  ///-Los cambios de precio y de descuento programados que todavia no se aplicaron, la cantidad de cambios programados y el historial de precios de cada categoria
+  ///-El owner guarda la direccion del duenio del contrato, y la direccion propuesta como nuevo owner con el vencimiento de la propuesta
+  ///-La pausa de emergencia, si el owner pauso el contrato, con el motivo y hasta cuando dura
+  ///-La version del formato de los socios y los pagos guardados, hasta donde se llego migrandolos a la version actual y cuantos registros se migran por llamada
+  ///-Los roles de cada direccion, que indican que operaciones puede realizar, y las direcciones que tienen un rol
+  ///-Las direcciones aprobadoras y las aprobaciones que necesita un cambio de configuracion, y las propuestas de cambios guardadas por id
   ///-El descuento que se le otorga al socio el proximo mes si llega a tener la cantidad correspondiente de pagos consecutivos sin atrasos
   ///-La cantidad de meses que el socio debe acumular de pagos consecutivos sin atrasos para obtener un descuento
-  
+  ///-Los recargos por pagar vencido: un recargo fijo que se cobra pasados ciertos dias del vencimiento, y un interes diario por mil del costo
+  ///-La tesoreria lleva el total de fondos que el club recibio por los pagos de los socios
+  ///-Los retiros de fondos que hizo el owner de la tesoreria
+  ///-Los reembolsos que quedaron pendientes de devolver por cada direccion, y el total de ellos
+  ///-Lo que transfirio cada direccion para cada pago, que es lo unico que se le reembolsa si se revierte el pago
+  ///-El saldo a favor de cada socio, que se descuenta de sus proximos pagos
+  ///-Las notas de credito de los pagos revertidos, guardadas por id, la cantidad de notas y los ids de las notas de cada pago
+  ///-El registro de actividades, guardadas por id, y la cantidad de actividades registradas
+  ///-El historial de cambios de categoria de cada socio
+  ///-Los grupos familiares, guardados por id, la cantidad de grupos creados y el grupo de cada socio que pertenece a uno
+  ///-El cursor de la facturacion, que indica desde que socio sigue la proxima llamada, y cuantos socios se procesan por llamada
+  ///-El ultimo periodo en que la facturacion le creo un pago a cada socio
+  ///Los campos que no son Mapping ni Lazy se guardan todos juntos en la raiz del contrato, con el formato de la primera version que se podia actualizar
+  ///con actualizar_codigo. Para que un contrato actualizado pueda seguir leyendo su raiz esos campos no se pueden agregar, sacar, cambiar ni reordenar,
+  ///y los campos nuevos van en un Mapping o en un Lazy, que se guardan cada uno con su propia clave y arrancan vacios en los contratos actualizados
+
   #[ink(storage)]
 	pub struct Club {
-  	socios: Vec<Socio>,
-  	pagos:Vec<Pago>,
-    precio_categoria: Mapping<TipoCategoria, u128>,
+  	socios: Mapping<u128, Socio>,
+    orden_socios: Mapping<u32, u128>,
+    cantidad_socios: u32,
+  	pagos: Mapping<u128, Pago>,
+    cantidad_pagos: u128,
+    pagos_socio: Mapping<u128, Vec<u128>>,
+    cuenta_de_socio: Mapping<u128, AccountId>,
+    socio_de_cuenta: Mapping<AccountId, u128>,
+    vinculos_pendientes: Mapping<AccountId, u128>,
+    categorias: Mapping<u32, Categoria>,
+    cantidad_categorias: u32,
+    cambios_programados: Vec<CambioProgramado>,
+    cantidad_cambios_programados: u32,
+    historial_precios: Mapping<u32, Vec<CambioPrecio>>,
     owner: Option<AccountId>,
-    direcciones: Vec<AccountId>,
-    politica: bool,
+    owner_propuesto: Option<(AccountId, u64)>,
+    pausa: Option<Pausa>,
+    version_storage: u32,
+    cursor_migracion: u32,
+    roles: Mapping<AccountId, Rol>,
+    cuentas_con_rol: Vec<AccountId>,
+    aprobadores: Vec<AccountId>,
+    umbral_aprobacion: u32,
+    propuestas: Mapping<u32, Propuesta>,
+    cantidad_propuestas: u32,
     descuento: u128,
     cantidad_meses: u128,
+    recargo_fijo: u128,
+    dias_recargo: u64,
+    interes_diario: u128,
+    tesoreria: Balance,
+    retiros: Vec<Retiro>,
+    reembolsos_pendientes: Mapping<AccountId, Balance>,
+    total_reembolsos_pendientes: Balance,
+    saldos_a_favor: Mapping<u128, Balance>,
+    notas_credito: Mapping<u128, NotaCredito>,
+    cantidad_notas_credito: u128,
+    notas_pago: Mapping<u128, Vec<u128>>,
+    cursor_facturacion: u32,
+    limite_facturacion: u32,
+    actividades: Mapping<u32, Actividad>,
+    cantidad_actividades: u32,
+    historial_categorias: Mapping<u128, Vec<CambioCategoria>>,
+    ids_migracion: Mapping<u128, u128>,
+    grupos: Mapping<u32, GrupoFamiliar>,
+    cantidad_grupos: Lazy<u32>,
+    grupo_de_socio: Mapping<u128, u32>,
+    limite_migracion: Lazy<u32>,
+    aportes_pago: Mapping<u128, Vec<Aporte>>,
+    periodo_facturado: Mapping<u128, u64>,
   }
-  #[derive(scale::Decode, scale::Encode,Debug,PartialEq)]
+  #[derive(scale::Decode, scale::Encode,Clone,Debug,PartialEq)]
   #[cfg_attr(
       feature = "std",
       derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
   )]
-  enum Actividad{
-  	FUTBOL, 
-    BASQUET, 
-    RUGBY, 
-    HOCKEY, 
-    NATACION, 
-  	TENIS,
-    PADDLE,
-    TODOS,
+  ///El struct Actividad guarda la informacion de cada actividad registrada en el club
+  ///de cada actividad se conoce su id, su nombre, la cuota extra y el cupo maximo de socios si tiene,
+  ///cuantos socios estan inscriptos y si esta activa, las actividades retiradas no se pueden elegir
+  pub struct Actividad{
+    id: u32,
+    nombre: String,
+    cuota_extra: Option<u128>,
+    cupo_maximo: Option<u32>,
+    inscriptos: u32,
+    activa: bool,
   }
   #[derive(scale::Decode, scale::Encode,Clone,Copy,PartialEq,Debug)]
   #[cfg_attr(
       feature = "std",
       derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
   )]
-  enum TipoCategoria{
-  	A,
-    B,
-    C,
+  ///El enum ActividadesIncluidas indica que actividades puede hacer un socio de una categoria
+  ///Todas: puede hacer todas las actividades del club
+  ///Cantidad: puede inscribirse en hasta esa cantidad de actividades, con 0 no tiene ninguna actividad
+  pub enum ActividadesIncluidas{
+    Todas,
+    Cantidad(u32),
+  }
+  #[derive(scale::Decode, scale::Encode,Clone,PartialEq,Debug)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct Categoria guarda la informacion de cada categoria del catalogo del club
+  ///de cada categoria se conoce su id, su nombre, el precio mensual, las actividades que incluye
+  ///y si esta activa, las categorias retiradas no aceptan socios nuevos pero sus socios siguen pagando
+  pub struct Categoria{
+    id: u32,
+    nombre: String,
+    precio: u128,
+    actividades: ActividadesIncluidas,
+    activa: bool,
   }
   #[derive(scale::Decode, scale::Encode,Debug,PartialEq)]
   #[cfg_attr(
@@ -62,17 +163,80 @@ mod club{
       derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
   )]
   ///En el Struct Pago se van a guardar los datos de los pagos de los socios
-  ///De cada pago se conoce su id(que es la posicion que ocupa en el vector),el dni del socio,
+  ///De cada pago se conoce su id(que es la posicion que ocupa en el vector),el id del socio (el hash de su dni),
   ///el costo del pago, la fecha de vencimiento, la fecha en la que fue pagado, 
-  ///un booleano que indica si fue pagado o no, y otro para indica si tiene descuento
+  ///un booleano que indica si fue pagado o no, otro para indica si tiene descuento,
+  ///el recargo por atraso que se cobro aparte del costo cuando se pago, el monto que se lleva pagado hasta ahora,
+  ///si la deuda del pago se paso a un plan de cuotas, si el pago es una cuota de un plan y la direccion que lo termino de pagar
   pub struct Pago{
   	id: u128,
-    dni_socio: u128,
+    id_socio: u128,
     costo: u128,
     fecha_pago: Option<u64>,
     fecha_vencimiento: u64,
     pagado:bool,
     tiene_descuento: bool,
+    recargo: u128,
+    monto_pagado: u128,
+    refinanciado: bool,
+    cuota_plan: bool,
+    pagador: Option<AccountId>,
+  }
+  #[derive(scale::Decode, scale::Encode,Clone,Debug,PartialEq)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct GrupoFamiliar guarda un grupo de socios que se factura en conjunto, como una familia
+  ///de cada grupo se conoce su id, el socio titular que se hace cargo de pagar, los socios a cargo del titular,
+  ///el descuento en porcentaje que se le hace a la cuota de todos los integrantes y si esta activo, los grupos disueltos ya no tienen integrantes
+  pub struct GrupoFamiliar{
+    id: u32,
+    titular: u128,
+    integrantes: Vec<u128>,
+    descuento: u128,
+    activo: bool,
+  }
+  ///Resumen de un pago que devuelve get_pago: id, fecha de vencimiento, si fue pagado, costo y recargo por atraso
+  pub type ResumenPago = (u128, u64, bool, u128, u128);
+  ///Lo que transfirio una direccion para un pago
+  type Aporte = (AccountId, Balance);
+  #[derive(scale::Decode, scale::Encode,Clone,Copy,Debug,PartialEq)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El enum Rol indica que puede hacer cada direccion en el club
+  ///-Owner: todo, incluido manejar los fondos y los roles. No se asigna, es el owner del contrato
+  ///-Administrador: todas las operaciones sobre socios, pagos, categorias, actividades y la configuracion
+  ///-Cajero: solo registrar pagos
+  ///-Auditor: solo consultar
+  ///-Socio: ninguna operacion que pida permiso. Solo usa las operaciones de autoservicio (pagar_mi_cuota, mi_estado_de_cuenta y pagar_mi_grupo),
+  /// que se controlan con la cuenta vinculada a su socio y solo llegan a los datos de ese socio
+  pub enum Rol{
+    Owner,
+    Administrador,
+    Cajero,
+    Auditor,
+    Socio,
+  }
+  ///El enum Permiso indica el tipo de permiso que necesita cada operacion
+  #[derive(Clone,Copy,PartialEq)]
+  enum Permiso{
+    Consultar,
+    RegistrarPagos,
+    Administrar,
+  }
+  #[derive(scale::Decode, scale::Encode,Clone,Copy,Debug,PartialEq)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El enum EstadoSocio indica si el socio esta activo, suspendido o si se dio de baja del club
+  pub enum EstadoSocio{
+    Activo,
+    Suspendido,
+    DadoDeBaja,
   }
   #[derive(scale::Decode, scale::Encode,PartialEq,Debug)]
   #[cfg_attr(
@@ -80,18 +244,21 @@ mod club{
       derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
   )]
   ///El struct Socio guarda la informacion de cada socio del club
-  ///de cada socio se conoce su dni, su categoria, la actividad, 
+  ///de cada socio se conoce su id (el hash de su dni), su categoria, los ids de las actividades en las que esta inscripto, 
   /// la fecha de registro,los pagos realizados y pendientes 
-  /// y el total de pagos sin atraso que tuvo el socio
-  
+  /// la racha de pagos seguidos sin atraso, la racha que tenia cuando se le dio el ultimo descuento,
+  /// su estado y el motivo de la suspension si esta suspendido
   pub struct Socio{
-    dni: u128,
-    categoria: TipoCategoria,  
-    actividad: Option<Actividad>,
+    id: u128,
+    categoria: u32,  
+    actividades: Vec<u32>,
     fecha_registro:u64,
     pagos_realizados:Vec<u128>,
     pagos_pendientes:Vec<u128>,
     pagos_sin_atrasos:u128,
+    racha_descuento:u128,
+    estado: EstadoSocio,
+    motivo_estado: Option<String>,
   }
   #[derive(scale::Decode, scale::Encode)]
   #[cfg_attr(
@@ -99,43 +266,834 @@ mod club{
       derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
   )]
   ///El struct Adicional nos guarda la informacion de un solo socio 
-  /// incluyendo el dni, la categoria y los montos pagados
- 
+  /// incluyendo el id, la categoria y los montos pagados
   pub struct Adicional{
-    dni:Option<u128>,
-    cate:Option<TipoCategoria>,
+    id_socio:Option<u128>,
+    cate:Option<String>,
     p:Vec<u128>,
   }
+  #[derive(scale::Encode, scale::Decode,Debug,PartialEq,Eq,Clone,Copy)]
+  #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+  ///El enum ClubError tiene los errores que pueden devolver las operaciones del club
+  pub enum ClubError{
+    ///Quien llama no tiene permiso para realizar la operacion
+    NoAutorizado,
+    ///No existe un socio con ese id
+    SocioNoEncontrado,
+    ///Ya existe un socio con ese id
+    SocioExistente,
+    ///La categoria no es una de las del catalogo del club o esta retirada
+    CategoriaInvalida,
+    ///Ya existe una categoria con ese nombre
+    CategoriaExistente,
+    ///La actividad no esta registrada en el club o esta retirada
+    ActividadInvalida,
+    ///Ya existe una actividad con ese nombre
+    ActividadExistente,
+    ///La actividad no tiene mas lugar para socios nuevos
+    CupoCompleto,
+    ///La categoria del socio no le permite inscribirse en mas actividades
+    LimiteActividades,
+    ///El socio ya esta inscripto en esa actividad
+    YaInscripto,
+    ///El socio no esta inscripto en esa actividad
+    NoInscripto,
+    ///El socio ya tiene esa categoria
+    MismaCategoria,
+    ///El socio no esta activo, por lo que no se puede realizar la operacion
+    SocioInactivo,
+    ///El socio no esta en un estado que permita ese cambio de estado
+    EstadoInvalido,
+    ///El monto transferido no alcanza para cubrir el pago
+    MontoIncorrecto,
+    ///El socio no tiene pagos pendientes
+    SinPagosPendientes,
+    ///La direccion ya tiene ese rol
+    DireccionExistente,
+    ///La direccion no tiene un rol, por lo que no se le puede quitar
+    DireccionNoEncontrada,
+    ///El rol Owner no se puede asignar
+    RolInvalido,
+    ///No hay una propuesta de nuevo owner pendiente
+    SinPropuestaPendiente,
+    ///La propuesta de nuevo owner o de cambio de configuracion ya vencio
+    PropuestaVencida,
+    ///No existe una propuesta de cambio de configuracion con ese id
+    PropuestaNoEncontrada,
+    ///La propuesta de cambio de configuracion ya se ejecuto
+    PropuestaEjecutada,
+    ///La fecha desde la que rige un cambio programado tiene que ser posterior a la actual
+    FechaInvalida,
+    ///No existe un cambio programado pendiente con ese id
+    CambioNoEncontrado,
+    ///Quien llama ya aprobo esa propuesta
+    YaAprobada,
+    ///La cantidad de aprobaciones necesarias tiene que ser al menos 1 y no mas que la cantidad de aprobadores
+    UmbralInvalido,
+    ///Con la aprobacion multiple activa, el cambio se tiene que hacer con una propuesta
+    RequiereAprobacion,
+    ///El contrato esta en pausa, por lo que no se puede realizar la operacion
+    ContratoEnPausa,
+    ///El contrato no esta en pausa
+    ContratoNoPausado,
+    ///Quien llama no tiene un socio vinculado a su cuenta
+    CuentaNoVinculada,
+    ///La cuenta ya esta vinculada a un socio
+    CuentaYaVinculada,
+    ///El socio ya tiene una cuenta vinculada
+    SocioYaVinculado,
+    ///La cuenta no pidio vincularse a ningun socio
+    SinVinculoPendiente,
+    ///Los socios y los pagos guardados todavia no se migraron a la version actual, hay que llamar a migrar
+    MigracionPendiente,
+    ///Los socios y los pagos guardados ya estan en la version actual
+    SinMigracionPendiente,
+    ///No se pudo reemplazar el codigo del contrato
+    ActualizacionFallida,
+    ///No hay un socio en esa posicion
+    PosicionInvalida,
+    ///No hay fondos disponibles suficientes en la tesoreria
+    FondosInsuficientes,
+    ///No hay reembolsos pendientes para quien llama
+    SinReembolsosPendientes,
+    ///No se pudo realizar la transferencia
+    TransferenciaFallida,
+    ///Ya existe un pago con ese id
+    PagoExistente,
+    ///La cantidad de cuotas o los dias entre cuotas no pueden ser 0
+    CuotasInvalidas,
+    ///No existe un pago con ese id
+    PagoNoEncontrado,
+    ///No se cargo el id de un socio guardado por dni, hay que cargarlo con cargar_ids_migracion antes de migrar
+    IdNoCargado,
+    ///El pago no esta pagado, por lo que no se puede revertir
+    PagoNoPagado,
+    ///No existe un grupo familiar activo con ese id
+    GrupoNoEncontrado,
+    ///El socio ya pertenece a un grupo familiar
+    SocioEnGrupo,
+    ///El socio no es un integrante a cargo del titular de ese grupo
+    NoIntegrante,
+    ///El titular no se puede quitar de su grupo, hay que disolverlo
+    EsTitular,
+    ///El socio no es el titular de un grupo familiar
+    NoEsTitular,
+    ///El descuento del grupo tiene que ser un porcentaje de 0 a 100
+    DescuentoInvalido,
+    ///Un contador supera el rango que puede recorrer la operacion
+    Desbordamiento,
+    ///El limite de registros por llamada no puede ser 0
+    LimiteInvalido,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct Retiro guarda la informacion de cada retiro de fondos de la tesoreria
+  ///de cada retiro se conoce el monto, la fecha, quien lo hizo y a que direccion se envio
+  pub struct Retiro{
+    monto: Balance,
+    fecha: u64,
+    caller: AccountId,
+    destino: AccountId,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct CambioCategoria guarda cada cambio de categoria de un socio
+  ///de cada cambio se conoce la categoria anterior y la nueva, la fecha,
+  ///y el costo del pago pendiente antes y despues del prorrateo si el socio tenia uno
+  pub struct CambioCategoria{
+    anterior: u32,
+    nueva: u32,
+    fecha: u64,
+    costo_anterior: Option<u128>,
+    costo_nuevo: Option<u128>,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct NotaCredito guarda la reversion de un pago, y una vez creada no se modifica
+  ///de cada nota se conoce su id, el pago revertido, el id del socio, el monto que se habia pagado,
+  ///el motivo, la fecha, si el monto se reembolso y quien la hizo
+  pub struct NotaCredito{
+    id: u128,
+    id_pago: u128,
+    id_socio: u128,
+    monto: Balance,
+    motivo: String,
+    fecha: u64,
+    reembolsado: bool,
+    caller: AccountId,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El enum CambioConfiguracion indica los cambios de configuracion que, con la aprobacion multiple activa,
+  ///solo se pueden hacer con una propuesta aprobada por los aprobadores
+  ///-Descuento: el descuento nuevo que se otorga a los socios
+  ///-CantidadMeses: la cantidad de meses nueva para obtener el descuento
+  ///-PrecioCategoria: el id de una categoria y su precio nuevo
+  ///-AsignarRol y QuitarRol: los cambios en los roles de las direcciones
+  ///-Aprobadores: las direcciones aprobadoras nuevas y las aprobaciones que se necesitan, con una lista vacia y 0 se desactiva la aprobacion multiple
+  ///-Programado: un cambio de precio o de descuento que rige desde la fecha indicada
+  pub enum CambioConfiguracion{
+    Descuento(u128),
+    CantidadMeses(u128),
+    PrecioCategoria(u32, u128),
+    AsignarRol(AccountId, Rol),
+    QuitarRol(AccountId),
+    Aprobadores(Vec<AccountId>, u32),
+    Programado(ValorProgramado, u64),
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone,Copy)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El enum ValorProgramado indica que valor cambia un cambio programado
+  ///-PrecioCategoria: el id de una categoria y su precio nuevo
+  ///-Descuento: el descuento nuevo que se otorga a los socios
+  pub enum ValorProgramado{
+    PrecioCategoria(u32, u128),
+    Descuento(u128),
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct CambioProgramado guarda un cambio de precio o de descuento anunciado con anticipacion
+  ///de cada cambio se conoce su id, el valor nuevo, la fecha desde la que rige y quien lo programo
+  pub struct CambioProgramado{
+    id: u32,
+    valor: ValorProgramado,
+    fecha_vigencia: u64,
+    caller: AccountId,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq)]
+  #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+  ///El struct EstadoCuenta es lo que ve un socio de su propia cuenta con mi_estado_de_cuenta
+  ///su id, el nombre de su categoria, su estado, el resumen de sus pagos, su saldo a favor
+  ///y, si debe algo, lo que le falta pagar del costo y del recargo de su proximo pago pendiente
+  pub struct EstadoCuenta{
+    id_socio: u128,
+    categoria: String,
+    estado: EstadoSocio,
+    pagos: Vec<ResumenPago>,
+    saldo_a_favor: Balance,
+    monto_a_pagar: Option<(u128, u128)>,
+  }
+  ///El formato en que se guardaban los socios en la version 1, antes del estado, el motivo y la racha del ultimo descuento
+  ///Solo se usa para leer los socios viejos al migrar
+  #[derive(scale::Decode, scale::Encode)]
+  struct SocioV1{
+    dni: u128,
+    categoria: u32,
+    actividades: Vec<u32>,
+    fecha_registro:u64,
+    pagos_realizados:Vec<u128>,
+    pagos_pendientes:Vec<u128>,
+    pagos_sin_atrasos:u128,
+  }
+  ///El formato en que se guardaban los pagos en la version 1, antes del recargo, los pagos parciales, los planes de cuotas y el pagador
+  ///Solo se usa para leer los pagos viejos al migrar
+  #[derive(scale::Decode, scale::Encode)]
+  struct PagoV1{
+    id: u128,
+    dni_socio: u128,
+    costo: u128,
+    fecha_pago: Option<u64>,
+    fecha_vencimiento: u64,
+    pagado:bool,
+    tiene_descuento: bool,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct Pausa guarda la pausa de emergencia del contrato
+  ///de la pausa se conoce el motivo, quien la hizo, desde cuando y, si tiene, la fecha en que se reanuda sola
+  pub struct Pausa{
+    motivo: String,
+    caller: AccountId,
+    desde: u64,
+    hasta: Option<u64>,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct CambioPrecio guarda cada cambio del precio de una categoria, para poder auditarlos
+  ///de cada cambio se conoce el precio anterior, el nuevo y la fecha desde la que rige
+  pub struct CambioPrecio{
+    anterior: u128,
+    nuevo: u128,
+    fecha: u64,
+  }
+  #[derive(scale::Decode, scale::Encode,Debug,PartialEq,Clone)]
+  #[cfg_attr(
+      feature = "std",
+      derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+  )]
+  ///El struct Propuesta guarda un cambio de configuracion propuesto por un aprobador
+  ///de cada propuesta se conoce su id, el cambio, quien la propuso, quienes la aprobaron,
+  ///la fecha en que vence y si ya se ejecuto
+  pub struct Propuesta{
+    id: u32,
+    cambio: CambioConfiguracion,
+    proponente: AccountId,
+    aprobaciones: Vec<AccountId>,
+    vencimiento: u64,
+    ejecutada: bool,
+  }
+  ///Se emite cuando se registra un nuevo socio en el club
+  #[ink(event)]
+  pub struct SocioRegistrado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    categoria: u32,
+  }
+  ///Se emite cuando se crea un pago pendiente para un socio
+  #[ink(event)]
+  pub struct PagoCreado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    id: u128,
+    costo: u128,
+    fecha_vencimiento: u64,
+  }
+  ///Se emite cuando un socio paga una parte de un pago pendiente, con lo que le falta para saldarlo
+  #[ink(event)]
+  pub struct PagoParcialRegistrado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    id: u128,
+    monto: Balance,
+    restante: Balance,
+  }
+  ///Se emite cuando se arma un plan de cuotas para la deuda de un socio, con los ids de las cuotas
+  #[ink(event)]
+  pub struct PlanCuotasCreado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    deuda: Balance,
+    cuotas: Vec<u128>,
+  }
+  ///Se emite cuando un socio paga uno de sus pagos pendientes
+  #[ink(event)]
+  pub struct PagoRegistrado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    id: u128,
+    monto: Balance,
+  }
+  ///Se emite cuando un pago nuevo tiene el descuento por pagar sin atrasos
+  #[ink(event)]
+  pub struct DescuentoAplicado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    id: u128,
+    descuento: u128,
+  }
+  ///Se emite cuando cambia el descuento o la cantidad de meses necesaria para obtenerlo
+  #[ink(event)]
+  pub struct DescuentoCambiado{
+    #[ink(topic)]
+    caller: AccountId,
+    descuento: u128,
+    cantidad_meses: u128,
+  }
+  ///Se emite cuando se cambian los recargos por pagar vencido
+  #[ink(event)]
+  pub struct RecargosCambiados{
+    #[ink(topic)]
+    caller: AccountId,
+    recargo_fijo: u128,
+    dias_recargo: u64,
+    interes_diario: u128,
+  }
+  ///Se emite cuando se crea una categoria nueva en el catalogo
+  #[ink(event)]
+  pub struct CategoriaCreada{
+    #[ink(topic)]
+    caller: AccountId,
+    id: u32,
+    nombre: String,
+    precio: u128,
+  }
+  ///Se emite cuando se edita una categoria del catalogo
+  #[ink(event)]
+  pub struct CategoriaEditada{
+    #[ink(topic)]
+    caller: AccountId,
+    id: u32,
+    nombre: String,
+    precio: u128,
+  }
+  ///Se emite cuando se retira una categoria del catalogo
+  #[ink(event)]
+  pub struct CategoriaRetirada{
+    #[ink(topic)]
+    caller: AccountId,
+    id: u32,
+  }
+  ///Se emite cuando se registra una actividad nueva
+  #[ink(event)]
+  pub struct ActividadCreada{
+    #[ink(topic)]
+    caller: AccountId,
+    id: u32,
+    nombre: String,
+  }
+  ///Se emite cuando se edita una actividad registrada
+  #[ink(event)]
+  pub struct ActividadEditada{
+    #[ink(topic)]
+    caller: AccountId,
+    id: u32,
+    nombre: String,
+  }
+  ///Se emite cuando se retira una actividad
+  #[ink(event)]
+  pub struct ActividadRetirada{
+    #[ink(topic)]
+    caller: AccountId,
+    id: u32,
+  }
+  ///Se emite cuando se suspende a un socio
+  #[ink(event)]
+  pub struct SocioSuspendido{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    motivo: String,
+  }
+  ///Se emite cuando un socio se da de baja, con el monto que salda de sus pagos pendientes
+  #[ink(event)]
+  pub struct SocioDadoDeBaja{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    monto: Balance,
+  }
+  ///Se emite cuando un socio suspendido o dado de baja vuelve a estar activo
+  #[ink(event)]
+  pub struct SocioReincorporado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+  }
+  ///Se emite cuando un socio cambia de categoria
+  #[ink(event)]
+  pub struct CategoriaCambiada{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    anterior: u32,
+    nueva: u32,
+  }
+  ///Se emite cuando un socio se inscribe en una actividad mas
+  #[ink(event)]
+  pub struct ActividadAgregada{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    actividad: u32,
+  }
+  ///Se emite cuando un socio se da de baja de una actividad
+  #[ink(event)]
+  pub struct ActividadQuitada{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    actividad: u32,
+  }
+  ///Se emite cuando se le asigna o cambia el rol a una direccion
+  #[ink(event)]
+  pub struct RolAsignado{
+    #[ink(topic)]
+    direccion: AccountId,
+    #[ink(topic)]
+    caller: AccountId,
+    rol: Rol,
+  }
+  ///Se emite cuando se le quita el rol a una direccion
+  #[ink(event)]
+  pub struct RolQuitado{
+    #[ink(topic)]
+    direccion: AccountId,
+    #[ink(topic)]
+    caller: AccountId,
+  }
+  ///Se emite cuando el owner propone una direccion como nuevo owner
+  #[ink(event)]
+  pub struct OwnerPropuesto{
+    #[ink(topic)]
+    actual: AccountId,
+    #[ink(topic)]
+    propuesto: AccountId,
+    vencimiento: u64,
+  }
+  ///Se emite cuando el owner cancela la propuesta de nuevo owner
+  #[ink(event)]
+  pub struct PropuestaOwnerCancelada{
+    #[ink(topic)]
+    caller: AccountId,
+    propuesto: AccountId,
+  }
+  ///Se emite cuando el contrato pasa a tener un nuevo owner
+  #[ink(event)]
+  pub struct OwnerCambiado{
+    #[ink(topic)]
+    anterior: AccountId,
+    #[ink(topic)]
+    nuevo: AccountId,
+  }
+  ///Se emite cuando cambian las direcciones aprobadoras o las aprobaciones que necesita un cambio de configuracion
+  #[ink(event)]
+  pub struct AprobadoresCambiados{
+    #[ink(topic)]
+    caller: AccountId,
+    aprobadores: Vec<AccountId>,
+    umbral: u32,
+  }
+  ///Se emite cuando un aprobador propone un cambio de configuracion
+  #[ink(event)]
+  pub struct CambioPropuesto{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    proponente: AccountId,
+    vencimiento: u64,
+  }
+  ///Se emite cuando un aprobador aprueba una propuesta de cambio de configuracion
+  #[ink(event)]
+  pub struct CambioAprobado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    aprobador: AccountId,
+    aprobaciones: u32,
+  }
+  ///Se emite cuando una propuesta alcanza las aprobaciones necesarias y se ejecuta el cambio
+  #[ink(event)]
+  pub struct CambioEjecutado{
+    #[ink(topic)]
+    id: u32,
+    caller: AccountId,
+  }
+  ///Se emite cuando se programa un cambio de precio o de descuento
+  #[ink(event)]
+  pub struct CambioProgramadoCreado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    caller: AccountId,
+    valor: ValorProgramado,
+    fecha_vigencia: u64,
+  }
+  ///Se emite cuando se cancela un cambio programado antes de que rija
+  #[ink(event)]
+  pub struct CambioProgramadoCancelado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    caller: AccountId,
+  }
+  ///Se emite cuando el owner pausa el contrato
+  #[ink(event)]
+  pub struct ContratoPausado{
+    #[ink(topic)]
+    caller: AccountId,
+    motivo: String,
+    hasta: Option<u64>,
+  }
+  ///Se emite cuando el owner reanuda el contrato pausado
+  #[ink(event)]
+  pub struct ContratoReanudado{
+    #[ink(topic)]
+    caller: AccountId,
+  }
+  ///Se emite cuando el owner reemplaza el codigo del contrato
+  #[ink(event)]
+  pub struct CodigoActualizado{
+    #[ink(topic)]
+    caller: AccountId,
+    code_hash: [u8; 32],
+  }
+  ///Se emite cuando se terminan de migrar los socios y los pagos a la version actual
+  #[ink(event)]
+  pub struct StorageMigrado{
+    #[ink(topic)]
+    caller: AccountId,
+    anterior: u32,
+    version: u32,
+  }
+  ///Se emite cuando una cuenta pide vincularse a un socio
+  #[ink(event)]
+  pub struct VinculoSolicitado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    cuenta: AccountId,
+  }
+  ///Se emite cuando se aprueba el vinculo de una cuenta con un socio
+  #[ink(event)]
+  pub struct CuentaVinculada{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    cuenta: AccountId,
+    caller: AccountId,
+  }
+  ///Se emite cuando se desvincula la cuenta de un socio
+  #[ink(event)]
+  pub struct CuentaDesvinculada{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    cuenta: AccountId,
+    caller: AccountId,
+  }
+  ///Se emite cuando el owner retira fondos de la tesoreria
+  #[ink(event)]
+  pub struct FondosRetirados{
+    #[ink(topic)]
+    caller: AccountId,
+    #[ink(topic)]
+    destino: AccountId,
+    monto: Balance,
+  }
+  ///Se emite cada vez que se procesa una tanda de la facturacion del periodo, con cuantos pagos se crearon y a cuantos socios no se les pudo crear
+  #[ink(event)]
+  pub struct FacturacionProcesada{
+    #[ink(topic)]
+    caller: AccountId,
+    desde: u32,
+    hasta: u32,
+    creados: u32,
+    fallidos: u32,
+    terminado: bool,
+  }
+  ///Se emite cuando la facturacion del periodo no le puede crear el pago a un socio, con el error, y la facturacion sigue con los demas
+  #[ink(event)]
+  pub struct SocioNoFacturado{
+    #[ink(topic)]
+    id_socio: u128,
+    error: ClubError,
+  }
+  ///Se emite cuando se suma saldo a favor de un socio, por un excedente de pago o acreditado por el owner
+  #[ink(event)]
+  pub struct SaldoAcreditado{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    monto: Balance,
+    motivo: String,
+  }
+  ///Se emite cuando se usa saldo a favor de un socio para cubrir un pago
+  #[ink(event)]
+  pub struct SaldoAplicado{
+    #[ink(topic)]
+    id_socio: u128,
+    id: u128,
+    monto: Balance,
+  }
+  ///Se emite cuando se revierte un pago, con la nota de credito creada y si se reembolso lo pagado
+  #[ink(event)]
+  pub struct PagoRevertido{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    caller: AccountId,
+    id_pago: u128,
+    nota: u128,
+    monto: Balance,
+    reembolsado: bool,
+  }
+  ///Se emite cuando una direccion reclama el reembolso que tenia pendiente
+  #[ink(event)]
+  pub struct ReembolsoReclamado{
+    #[ink(topic)]
+    caller: AccountId,
+    monto: Balance,
+  }
+  ///Se emite cuando se le devuelve a un socio que se da de baja lo que le sobro, a la cuenta vinculada a su socio
+  ///Si la transferencia fallo el monto queda como reembolso pendiente de esa cuenta
+  #[ink(event)]
+  pub struct SaldoDevuelto{
+    #[ink(topic)]
+    id_socio: u128,
+    #[ink(topic)]
+    cuenta: AccountId,
+    monto: Balance,
+  }
+  ///Se emite cuando se crea un grupo familiar
+  #[ink(event)]
+  pub struct GrupoCreado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    titular: u128,
+    caller: AccountId,
+    descuento: u128,
+  }
+  ///Se emite cuando se suma un socio a cargo del titular de un grupo familiar
+  #[ink(event)]
+  pub struct IntegranteAgregado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    id_socio: u128,
+    caller: AccountId,
+  }
+  ///Se emite cuando un socio deja de estar a cargo del titular de un grupo familiar
+  #[ink(event)]
+  pub struct IntegranteQuitado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    id_socio: u128,
+    caller: AccountId,
+  }
+  ///Se emite cuando cambia el descuento de un grupo familiar
+  #[ink(event)]
+  pub struct DescuentoGrupoCambiado{
+    #[ink(topic)]
+    id: u32,
+    caller: AccountId,
+    descuento: u128,
+  }
+  ///Se emite cuando se disuelve un grupo familiar
+  #[ink(event)]
+  pub struct GrupoDisuelto{
+    #[ink(topic)]
+    id: u32,
+    caller: AccountId,
+  }
+  ///Se emite cuando se paga la deuda de un grupo familiar en una sola transferencia, con el monto transferido
+  #[ink(event)]
+  pub struct PagoGrupoRegistrado{
+    #[ink(topic)]
+    id: u32,
+    #[ink(topic)]
+    caller: AccountId,
+    monto: Balance,
+  }
   impl Club {
     ///Nos crea una instancia del Club
-    ///creamos dos vectores, uno de los socios y el otro para los pagos
-    ///Inicializamos las categorias con sus respectivos precios
+    ///creamos los mapping de los socios y de los pagos, vacios
+    ///Inicializamos el catalogo con las categorias A, B y C con sus respectivos precios y actividades
+    ///Registramos las actividades del club, sin cuota extra ni cupo maximo
     ///Indicamos el owner
-    ///La politica la inicializamos como verdadera para q solo las direcciones autorizadas realicen operaciones
+    ///Arranca sin roles asignados, asi que solo el owner puede realizar operaciones hasta que le asigne roles a otras direcciones
+    ///Arranca sin aprobadores, asi que los cambios de configuracion no necesitan aprobacion multiple hasta que el owner los configure
   	///Inicializamos la cantidad de meses que los socios tiene que tener los pagos consecutivos sin atrasos para obtener el descuento
     ///Incializamos el descuento que se les otorga en el proximo mes
+    ///Inicializamos la facturacion con el cursor en 0 y un limite de 50 socios por llamada
   	#[ink(constructor)]
     pub fn new()-> Self{
-    	let socios:Vec<Socio> = Vec::new();
-      let pagos:Vec<Pago> = Vec::new();
-      let mut precio_categoria = Mapping::new();
-      precio_categoria.insert(TipoCategoria::A, &5000);
-      precio_categoria.insert(TipoCategoria::B, &3000);
-      precio_categoria.insert(TipoCategoria::C, &2000);
-      let direcciones:Vec<AccountId>=Vec::new();
+    	let socios = Mapping::new();
+      let orden_socios = Mapping::new();
+      let pagos = Mapping::new();
+      let pagos_socio = Mapping::new();
+      let mut categorias = Mapping::new();
+      categorias.insert(1, &Categoria::crear_categoria(1, "A".to_string(), 5000, ActividadesIncluidas::Todas));
+      categorias.insert(2, &Categoria::crear_categoria(2, "B".to_string(), 3000, ActividadesIncluidas::Cantidad(1)));
+      categorias.insert(3, &Categoria::crear_categoria(3, "C".to_string(), 2000, ActividadesIncluidas::Cantidad(0)));
+      let mut actividades = Mapping::new();
+      let nombres = ["FUTBOL", "BASQUET", "RUGBY", "HOCKEY", "NATACION", "TENIS", "PADDLE"];
+      for (i, nombre) in nombres.iter().enumerate(){
+        let id = i as u32 + 1;
+        actividades.insert(id, &Actividad::crear_actividad(id, nombre.to_string(), None, None));
+      }
       let owner = None;
-      let politica = true;
       let descuento = 30;
       let cantidad_meses = 3;
+      let tesoreria = 0;
+      let retiros:Vec<Retiro> = Vec::new();
+      let reembolsos_pendientes = Mapping::new();
+      let total_reembolsos_pendientes = 0;
       let mut club = Self{
       	socios,
+        orden_socios,
+        cantidad_socios: 0,
         pagos,
-        precio_categoria,
-        direcciones,
+        cantidad_pagos: 0,
+        pagos_socio,
+        cuenta_de_socio: Mapping::new(),
+        socio_de_cuenta: Mapping::new(),
+        vinculos_pendientes: Mapping::new(),
+        categorias,
+        cantidad_categorias: 3,
+        cambios_programados: Vec::new(),
+        cantidad_cambios_programados: 0,
+        historial_precios: Mapping::new(),
+        roles: Mapping::new(),
+        cuentas_con_rol: Vec::new(),
+        aprobadores: Vec::new(),
+        umbral_aprobacion: 0,
+        propuestas: Mapping::new(),
+        cantidad_propuestas: 0,
         owner,
-        politica,
+        owner_propuesto: None,
+        pausa: None,
+        version_storage: VERSION_STORAGE,
+        cursor_migracion: 0,
         descuento,
         cantidad_meses,
+        recargo_fijo: 0,
+        dias_recargo: 0,
+        interes_diario: 0,
+        tesoreria,
+        retiros,
+        reembolsos_pendientes,
+        total_reembolsos_pendientes,
+        saldos_a_favor: Mapping::new(),
+        notas_credito: Mapping::new(),
+        cantidad_notas_credito: 0,
+        notas_pago: Mapping::new(),
+        cursor_facturacion: 0,
+        limite_facturacion: 50,
+        actividades,
+        cantidad_actividades: 7,
+        historial_categorias: Mapping::new(),
+        ids_migracion: Mapping::new(),
+        grupos: Mapping::new(),
+        cantidad_grupos: Lazy::new(),
+        grupo_de_socio: Mapping::new(),
+        limite_migracion: Lazy::new(),
+        aportes_pago: Mapping::new(),
+        periodo_facturado: Mapping::new(),
       };
       club.guardar_firma();
       club
@@ -163,504 +1121,2622 @@ mod club{
       }
       ok
     }
-    ///Cambia la direccion del owner, que se recibe como parámetro, solo si el owner es quien lo hace
+    ///Propone una direccion como nuevo owner, solo si quien llama es el owner
+    ///La propuesta vence a los 7 dias, y hasta que la direccion propuesta no la acepte el owner sigue siendo el mismo
+    ///Si ya habia una propuesta pendiente, la reemplaza
+    ///Devuelve el error NoAutorizado si quien llama no es el owner
     ///Ejemplo
     ///'''
-    ///  
-    /// self.set_ower(account_id);
-    /// 
+    /// self.proponer_owner(account_id)?;
     ///'''
     #[ink(message)]
-    pub fn set_owner(&mut self, clave:AccountId){
-      if self.es_owner(){
-        self.owner = Some(clave);
+    pub fn proponer_owner(&mut self, nuevo: AccountId)->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      let vencimiento = self.env().block_timestamp() + DIAS_PROPUESTA_OWNER * 24 * 60 * 60;
+      self.owner_propuesto = Some((nuevo, vencimiento));
+      let actual = self.env().caller();
+      self.env().emit_event(OwnerPropuesto{ actual, propuesto: nuevo, vencimiento });
+      Ok(())
+    }
+    ///La direccion propuesta acepta ser el nuevo owner, y desde ese momento el owner anterior pierde el control del contrato
+    ///Devuelve el error SinPropuestaPendiente si no hay propuesta, NoAutorizado si quien llama no es la direccion propuesta
+    ///o PropuestaVencida si ya pasaron los dias para aceptarla
+    ///Ejemplo
+    ///'''
+    /// self.aceptar_owner()?;
+    ///'''
+    #[ink(message)]
+    pub fn aceptar_owner(&mut self)->Result<(), ClubError>{
+      let (propuesto, vencimiento) = self.owner_propuesto.ok_or(ClubError::SinPropuestaPendiente)?;
+      let caller = self.env().caller();
+      if caller != propuesto{
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.env().block_timestamp() > vencimiento{
+        return Err(ClubError::PropuestaVencida);
+      }
+      let anterior = self.owner.unwrap_or(propuesto);
+      self.owner = Some(propuesto);
+      self.owner_propuesto = None;
+      self.env().emit_event(OwnerCambiado{ anterior, nuevo: propuesto });
+      Ok(())
+    }
+    ///Cancela la propuesta de nuevo owner pendiente, solo si quien llama es el owner
+    ///Devuelve el error NoAutorizado si no es el owner o SinPropuestaPendiente si no hay propuesta
+    ///Ejemplo
+    ///'''
+    /// self.cancelar_propuesta()?;
+    ///'''
+    #[ink(message)]
+    pub fn cancelar_propuesta(&mut self)->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      let (propuesto, _) = self.owner_propuesto.take().ok_or(ClubError::SinPropuestaPendiente)?;
+      let caller = self.env().caller();
+      self.env().emit_event(PropuestaOwnerCancelada{ caller, propuesto });
+      Ok(())
+    }
+    ///Devuelve la direccion propuesta como nuevo owner y la fecha en que vence la propuesta, o None si no hay propuesta
+    ///Ejemplo
+    ///'''
+    /// let propuesta = self.get_propuesta_owner();
+    ///'''
+    #[ink(message)]
+    pub fn get_propuesta_owner(&self)->Option<(AccountId, u64)>{
+      self.owner_propuesto
+    }
+    ///Pausa el contrato por una emergencia, solo si quien llama es el owner
+    ///Mientras esta en pausa no se puede realizar ninguna operacion que modifique el contrato, salvo el cambio de owner,
+    ///y las consultas siguen funcionando. Si se indica una fecha, el contrato se reanuda solo a partir de ella
+    ///Si ya estaba en pausa, reemplaza el motivo y la fecha
+    ///Devuelve el error NoAutorizado si no es el owner o FechaInvalida si la fecha no es posterior a la actual
+    ///Ejemplo
+    ///'''
+    /// self.pausar("Error en registro_pago".to_string(), None)?;
+    ///'''
+    #[ink(message)]
+    pub fn pausar(&mut self, motivo: String, hasta: Option<u64>)->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      let desde = self.env().block_timestamp();
+      if hasta.is_some_and(|h| h <= desde){
+        return Err(ClubError::FechaInvalida);
+      }
+      let caller = self.env().caller();
+      self.pausa = Some(Pausa{ motivo: motivo.clone(), caller, desde, hasta });
+      self.env().emit_event(ContratoPausado{ caller, motivo, hasta });
+      Ok(())
+    }
+    ///Reanuda el contrato pausado, solo si quien llama es el owner
+    ///Devuelve el error NoAutorizado si no es el owner o ContratoNoPausado si no esta en pausa
+    ///Ejemplo
+    ///'''
+    /// self.reanudar()?;
+    ///'''
+    #[ink(message)]
+    pub fn reanudar(&mut self)->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.esta_pausado(){
+        return Err(ClubError::ContratoNoPausado);
+      }
+      self.pausa = None;
+      let caller = self.env().caller();
+      self.env().emit_event(ContratoReanudado{ caller });
+      Ok(())
+    }
+    ///Devuelve la pausa del contrato si esta en pausa, o None si no lo esta o si ya paso la fecha en que se reanudaba
+    ///Ejemplo
+    ///'''
+    /// let pausa = self.get_pausa();
+    ///'''
+    #[ink(message)]
+    pub fn get_pausa(&self)->Option<Pausa>{
+      self.pausa.clone().filter(|_| self.esta_pausado())
+    }
+    ///Se fija si el contrato esta en pausa, teniendo en cuenta la fecha en que se reanuda solo
+    fn esta_pausado(&self)->bool{
+      let ahora = self.env().block_timestamp();
+      self.pausa.as_ref().is_some_and(|p| p.hasta.is_none_or(|h| ahora < h))
+    }
+    ///Devuelve el error ContratoEnPausa si el contrato esta en pausa, o MigracionPendiente si los socios y los pagos guardados
+    ///todavia no estan en la version actual. Lo usan todas las operaciones que modifican el contrato
+    ///Ejemplo
+    ///'''
+    /// self.verificar_pausa()?;
+    ///'''
+    fn verificar_pausa(&self)->Result<(), ClubError>{
+      if self.esta_pausado(){
+        return Err(ClubError::ContratoEnPausa);
+      }
+      if self.version_storage < VERSION_STORAGE{
+        return Err(ClubError::MigracionPendiente);
+      }
+      Ok(())
+    }
+    ///Reemplaza el codigo del contrato por el del code hash recibido, manteniendo todos los datos guardados, solo si quien llama es el owner
+    ///Se puede usar aunque el contrato este en pausa, para corregir el error que llevo a pausarlo
+    ///Si el codigo nuevo cambia el formato de los socios o los pagos, despues hay que llamar a migrar
+    ///Devuelve el error NoAutorizado si no es el owner, o ActualizacionFallida si no existe un codigo con ese hash
+    ///Ejemplo
+    ///'''
+    /// self.actualizar_codigo(code_hash)?;
+    ///'''
+    #[ink(message)]
+    pub fn actualizar_codigo(&mut self, code_hash: [u8; 32])->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      ink::env::set_code_hash(&code_hash).map_err(|_| ClubError::ActualizacionFallida)?;
+      let caller = self.env().caller();
+      self.env().emit_event(CodigoActualizado{ caller, code_hash });
+      Ok(())
+    }
+    ///Devuelve la version del formato en que estan guardados los socios y los pagos, y la version que usa el codigo actual
+    ///Ejemplo
+    ///'''
+    /// let (guardada, actual) = self.get_version();
+    ///'''
+    #[ink(message)]
+    pub fn get_version(&self)->(u32, u32){
+      (self.version_storage, VERSION_STORAGE)
+    }
+    ///Migra los socios y los pagos guardados en un formato viejo a la version actual, solo si quien llama es el owner
+    ///Recorre primero los pagos y despues los socios, hasta "limite_migracion" registros por llamada para no pasarse del limite de gas
+    ///Si los socios estaban guardados por dni, los pasa a guardar con el id que el owner cargo con cargar_ids_migracion,
+    ///y los vinculos pedidos que no se aprobaron se pasan a los ids con cargar_ids_migracion
+    ///Devuelve true cuando termina de migrar todo, y hasta entonces el resto de las operaciones que modifican el contrato devuelven MigracionPendiente
+    ///Devuelve el error NoAutorizado si no es el owner, SinMigracionPendiente si ya esta todo en la version actual,
+    ///IdNoCargado si falta el id de un socio guardado por dni, SocioExistente si ese id ya lo usa otro socio
+    ///o Desbordamiento si hay mas pagos de los que se pueden recorrer
+    ///Ejemplo
+    ///'''
+    /// while !self.migrar()?{}
+    ///'''
+    #[ink(message)]
+    pub fn migrar(&mut self)->Result<bool, ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.version_storage >= VERSION_STORAGE{
+        return Err(ClubError::SinMigracionPendiente);
+      }
+      let pagos = u32::try_from(self.cantidad_pagos).map_err(|_| ClubError::Desbordamiento)?;
+      let total = pagos.checked_add(self.cantidad_socios).ok_or(ClubError::Desbordamiento)?;
+      let desde = self.cursor_migracion;
+      let limite = self.limite_migracion.get().unwrap_or(LIMITE_MIGRACION);
+      let hasta = desde.saturating_add(limite).min(total);
+      //los pagos se pasan primero al formato actual, asi al mover un socio sus pagos ya se pueden leer
+      for i in desde..hasta{
+        if i < pagos{
+          self.migrar_pago(i as u128 + 1);
+        }else if let Some(dni) = self.orden_socios.get(i - pagos){
+          self.migrar_socio(i - pagos, dni)?;
+        }
+      }
+      let terminado = hasta >= total;
+      if terminado{
+        let anterior = self.version_storage;
+        self.version_storage = VERSION_STORAGE;
+        self.cursor_migracion = 0;
+        let caller = self.env().caller();
+        self.env().emit_event(StorageMigrado{ caller, anterior, version: VERSION_STORAGE });
+      }else{
+        self.cursor_migracion = hasta;
+      }
+      Ok(terminado)
+    }
+    ///Cambia la cantidad maxima de registros que recorre cada llamada a migrar, solo si quien llama es el owner
+    ///Se puede usar con la migracion pendiente, para achicar las tandas si una llamada se pasa del limite de gas
+    ///Devuelve el error NoAutorizado si no es el owner, o LimiteInvalido si el limite es 0
+    ///Ejemplo
+    ///'''
+    /// self.set_limite_migracion(20)?;
+    ///'''
+    #[ink(message)]
+    pub fn set_limite_migracion(&mut self, limite: u32)->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if limite == 0{
+        return Err(ClubError::LimiteInvalido);
+      }
+      self.limite_migracion.set(&limite);
+      Ok(())
+    }
+    ///Carga los ids de los socios guardados por dni, para que migrar los pase a guardar por id, solo si quien llama es el owner
+    ///Recibe pares (dni, id), con el id calculado fuera de la cadena con calcular_id_socio, y se puede llamar por tandas
+    ///Los dni de estos socios ya estaban publicos en el storage, pero la sal no se expone y los socios nuevos no se pueden relacionar con su dni
+    ///Cada id cargado se borra cuando se migra su socio
+    ///Tambien recibe las cuentas que pidieron vincularse y todavia no se aprobaron, que se sacan de los eventos VinculoSolicitado porque
+    ///el contrato no puede recorrerlas: cada pedido pasa a apuntar al id de su socio si ya esta cargado, y si no se descarta y hay que volver a pedirlo
+    ///Conviene pasar las cuentas junto con los ids y antes de migrar, porque los ids cargados se borran al migrar cada socio
+    ///Devuelve el error NoAutorizado si no es el owner, o SinMigracionPendiente si los socios ya estan guardados por id
+    ///Ejemplo
+    ///'''
+    /// self.cargar_ids_migracion([(44851840, id)].to_vec(), [cuenta].to_vec())?;
+    ///'''
+    #[ink(message)]
+    pub fn cargar_ids_migracion(&mut self, pares: Vec<(u128, u128)>, cuentas: Vec<AccountId>)->Result<(), ClubError>{
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.version_storage >= 3{
+        return Err(ClubError::SinMigracionPendiente);
+      }
+      for (dni, id) in pares{
+        self.ids_migracion.insert(dni, &id);
+      }
+      for cuenta in cuentas{
+        //solo los pedidos que todavia apuntan a un socio guardado por dni
+        let Some(dni) = self.vinculos_pendientes.get(cuenta).filter(|dni| self.socios.contains(dni)) else { continue };
+        if let Some(id) = self.ids_migracion.get(dni){
+          self.vinculos_pendientes.insert(cuenta, &id);
+        }else{
+          self.vinculos_pendientes.remove(cuenta);
+        }
+      }
+      Ok(())
+    }
+    ///Lee un socio guardado en un formato viejo y lo vuelve a guardar en el formato actual
+    ///Si estaba en el formato de la version 1, el socio queda activo y la racha del ultimo descuento arranca en 0
+    ///Si estaba guardado por dni, lo pasa a guardar con el id cargado junto con sus pagos, sus notas de credito, su saldo a favor,
+    ///su historial de categorias y su cuenta vinculada, y borra el id cargado
+    fn migrar_socio(&mut self, pos: u32, dni: u128)->Result<(), ClubError>{
+      let Some(mut socio) = self.leer_socio_viejo(dni) else { return Ok(()) };
+      if self.version_storage < 3{
+        let id = self.ids_migracion.take(dni).ok_or(ClubError::IdNoCargado)?;
+        if self.existe_socio(id){
+          return Err(ClubError::SocioExistente);
+        }
+        socio.id = id;
+        self.socios.remove(dni);
+        self.orden_socios.insert(pos, &id);
+        if let Some(ids) = self.pagos_socio.take(dni){
+          for id_pago in &ids{
+            if let Some(mut pago) = self.pagos.get(id_pago){
+              pago.id_socio = id;
+              self.pagos.insert(id_pago, &pago);
+            }
+            for id_nota in self.notas_pago.get(id_pago).unwrap_or_default(){
+              if let Some(mut nota) = self.notas_credito.get(id_nota){
+                nota.id_socio = id;
+                self.notas_credito.insert(id_nota, &nota);
+              }
+            }
+          }
+          self.pagos_socio.insert(id, &ids);
+        }
+        if let Some(saldo) = self.saldos_a_favor.take(dni){
+          self.saldos_a_favor.insert(id, &saldo);
+        }
+        if let Some(historial) = self.historial_categorias.take(dni){
+          self.historial_categorias.insert(id, &historial);
+        }
+        if let Some(cuenta) = self.cuenta_de_socio.take(dni){
+          self.cuenta_de_socio.insert(id, &cuenta);
+          self.socio_de_cuenta.insert(cuenta, &id);
+        }
+      }
+      self.socios.insert(socio.id, &socio);
+      Ok(())
+    }
+    ///Lee un socio guardado por dni, en el formato de la version 1 o en el actual segun la version guardada
+    fn leer_socio_viejo(&self, dni: u128)->Option<Socio>{
+      if self.version_storage >= 2{
+        return self.socios.get(dni);
+      }
+      let clave = (self.socios.key(), dni);
+      if let Ok(Some(viejo)) = ink::env::get_contract_storage::<_, SocioV1>(&clave){
+        let socio = Socio{
+          id: viejo.dni,
+          categoria: viejo.categoria,
+          actividades: viejo.actividades,
+          fecha_registro: viejo.fecha_registro,
+          pagos_realizados: viejo.pagos_realizados,
+          pagos_pendientes: viejo.pagos_pendientes,
+          pagos_sin_atrasos: viejo.pagos_sin_atrasos,
+          racha_descuento: 0,
+          estado: EstadoSocio::Activo,
+          motivo_estado: None,
+        };
+        return Some(socio);
+      }
+      None
+    }
+    ///Lee un pago guardado en el formato de la version 1 y lo vuelve a guardar en el formato actual
+    ///Los pagos pagados quedan con el costo entero pagado, y ninguno tiene recargo, plan de cuotas ni pagador
+    ///El socio del pago se cambia despues, cuando se migra el socio
+    fn migrar_pago(&mut self, id: u128){
+      if self.version_storage >= 2{
+        return;
+      }
+      let clave = (self.pagos.key(), id);
+      if let Ok(Some(viejo)) = ink::env::get_contract_storage::<_, PagoV1>(&clave){
+        let mut pago = Pago::crear_pago(viejo.dni_socio, viejo.id, viejo.costo, viejo.fecha_vencimiento, viejo.fecha_pago, viejo.pagado, viejo.tiene_descuento);
+        if pago.pagado{
+          pago.monto_pagado = pago.costo;
+        }
+        self.pagos.insert(id, &pago);
+      }
+    }
+    ///Se fija si quien llama tiene el permiso que pide la operacion, devolviendo un booleano
+    ///El owner tiene todos los permisos, y el resto de las direcciones los que les da su rol
+    ///Ejemplo
+    ///'''
+    /// if self.tiene_permiso(Permiso::Consultar){
+    ///   let suma = 1 + 1;
+    /// }
+    ///'''
+    fn tiene_permiso(&self, permiso: Permiso)->bool{
+      if self.es_owner(){
+        return true;
+      }
+      self.roles.get(self.env().caller()).is_some_and(|rol| rol.permite(permiso))
+    }
+    ///Le asigna un rol a una direccion solo si quien llama es el owner, si la direccion ya tenia otro rol se lo cambia
+    /// Si la direccion ya tiene ese rol devuelve el error DireccionExistente, y si el rol es Owner devuelve RolInvalido porque el owner se cambia con proponer_owner
+    /// Si el que llama no es el owner, devuelve el error NoAutorizado, y con la aprobacion multiple activa devuelve RequiereAprobacion
+    ///Ejemplo
+    ///'''
+    /// self.asignar_rol(account_id, Rol::Cajero)?;
+    ///'''
+    #[ink(message)]
+    pub fn asignar_rol(&mut self, clave: AccountId, rol: Rol)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      self.aplicar_asignar_rol(clave, rol)
+    }
+    ///Le asigna el rol a la direccion sin fijarse quien llama, lo usan asignar_rol y las propuestas de cambio aprobadas
+    fn aplicar_asignar_rol(&mut self, clave: AccountId, rol: Rol)->Result<(), ClubError>{
+      if rol == Rol::Owner{
+        return Err(ClubError::RolInvalido);
+      }
+      match self.roles.get(clave){
+        Some(actual) if actual == rol => return Err(ClubError::DireccionExistente),
+        Some(_) => {},
+        None => self.cuentas_con_rol.push(clave),
+      }
+      self.roles.insert(clave, &rol);
+      let caller = self.env().caller();
+      self.env().emit_event(RolAsignado{ direccion: clave, caller, rol });
+      Ok(())
+    }
+    ///Le quita el rol a una direccion solo si el que llama es el owner y la direccion tiene un rol
+    /// Si el que llama es el owner pero la direccion no tiene rol, devuelve el error DireccionNoEncontrada
+    /// Si el que llama no es el owner, devuelve el error NoAutorizado, y con la aprobacion multiple activa devuelve RequiereAprobacion
+    /// Ejemplo
+    /// '''
+    ///  self.quitar_rol(account_id)?;
+    /// '''
+    #[ink(message)]
+    pub fn quitar_rol(&mut self, clave: AccountId)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      self.aplicar_quitar_rol(clave)
+    }
+    ///Le quita el rol a la direccion sin fijarse quien llama, lo usan quitar_rol y las propuestas de cambio aprobadas
+    fn aplicar_quitar_rol(&mut self, clave: AccountId)->Result<(), ClubError>{
+      if self.roles.take(clave).is_none(){
+        return Err(ClubError::DireccionNoEncontrada);
+      }
+      self.cuentas_con_rol.retain(|d| *d != clave);
+      let caller = self.env().caller();
+      self.env().emit_event(RolQuitado{ direccion: clave, caller });
+      Ok(())
+    }
+    ///Devuelve el rol de una direccion, Owner si es el owner, o None si no tiene ninguno
+    /// Ejemplo
+    /// '''
+    ///   assert_eq!(self.get_rol(account_id), Some(Rol::Cajero));
+    /// '''
+    #[ink(message)]
+    pub fn get_rol(&self, clave: AccountId)->Option<Rol>{
+      if self.owner == Some(clave){
+        return Some(Rol::Owner);
+      }
+      self.roles.get(clave)
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve todas las direcciones que tienen un rol asignado con su rol
+    /// si no devuelve un vector vacio
+    /// Ejemplo
+    /// '''
+    ///   let roles:Vec<(AccountId, Rol)> = self.get_roles();
+    /// '''
+    #[ink(message)]
+    pub fn get_roles(&self)->Vec<(AccountId, Rol)>{
+      let mut vec:Vec<(AccountId, Rol)>=Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        vec = self.cuentas_con_rol.iter().filter_map(|d| self.roles.get(d).map(|rol| (*d, rol))).collect();
+      }
+      vec
+    }
+    ///Se fija si esta activa la aprobacion multiple, que es cuando hay direcciones aprobadoras configuradas
+    fn aprobacion_multiple(&self)->bool{
+      !self.aprobadores.is_empty()
+    }
+    ///Se fija que los aprobadores no esten repetidos y que las aprobaciones necesarias esten entre 1 y la cantidad de aprobadores
+    ///Una lista vacia solo se acepta con 0 aprobaciones, y desactiva la aprobacion multiple
+    fn validar_aprobadores(aprobadores: &[AccountId], umbral: u32)->Result<(), ClubError>{
+      if aprobadores.iter().enumerate().any(|(i, a)| aprobadores[..i].contains(a)){
+        return Err(ClubError::DireccionExistente);
+      }
+      let valido = if aprobadores.is_empty() { umbral == 0 } else { umbral > 0 && umbral as usize <= aprobadores.len() };
+      if !valido{
+        return Err(ClubError::UmbralInvalido);
+      }
+      Ok(())
+    }
+    ///Configura las direcciones aprobadoras y cuantas aprobaciones necesita un cambio de configuracion, solo si quien llama es el owner
+    ///Desde ese momento los cambios de descuento, cantidad de meses, precios de categorias, roles y aprobadores se hacen con proponer_cambio
+    ///Devuelve el error NoAutorizado si no es el owner, RequiereAprobacion si la aprobacion multiple ya estaba activa,
+    ///DireccionExistente si hay aprobadores repetidos o UmbralInvalido si la cantidad de aprobaciones no es valida
+    ///Ejemplo
+    ///'''
+    /// self.configurar_aprobadores([cuenta1, cuenta2, cuenta3].to_vec(), 2)?;
+    ///'''
+    #[ink(message)]
+    pub fn configurar_aprobadores(&mut self, aprobadores: Vec<AccountId>, umbral: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      Self::validar_aprobadores(&aprobadores, umbral)?;
+      self.cambiar_aprobadores(aprobadores, umbral);
+      Ok(())
+    }
+    ///Guarda los aprobadores y las aprobaciones necesarias, y emite el evento
+    fn cambiar_aprobadores(&mut self, aprobadores: Vec<AccountId>, umbral: u32){
+      self.aprobadores = aprobadores.clone();
+      self.umbral_aprobacion = umbral;
+      let caller = self.env().caller();
+      self.env().emit_event(AprobadoresCambiados{ caller, aprobadores, umbral });
+    }
+    ///Devuelve las direcciones aprobadoras y cuantas aprobaciones necesita un cambio de configuracion
+    ///Ejemplo
+    ///'''
+    /// let (aprobadores, umbral) = self.get_aprobadores();
+    ///'''
+    #[ink(message)]
+    pub fn get_aprobadores(&self)->(Vec<AccountId>, u32){
+      (self.aprobadores.clone(), self.umbral_aprobacion)
+    }
+    ///Un aprobador propone un cambio de configuracion, que queda aprobado por el y vence a los 7 dias
+    ///Si con su aprobacion ya se alcanzan las necesarias, el cambio se ejecuta en el momento
+    ///Devuelve el id de la propuesta, o el error NoAutorizado si quien llama no es aprobador,
+    ///CategoriaInvalida si la categoria no existe, RolInvalido si el rol es Owner, o los errores de validar_aprobadores y validar_programado
+    ///Ejemplo
+    ///'''
+    /// let id = self.proponer_cambio(CambioConfiguracion::Descuento(20))?;
+    ///'''
+    #[ink(message)]
+    pub fn proponer_cambio(&mut self, cambio: CambioConfiguracion)->Result<u32, ClubError>{
+      self.verificar_pausa()?;
+      let caller = self.env().caller();
+      if !self.aprobadores.contains(&caller){
+        return Err(ClubError::NoAutorizado);
+      }
+      match &cambio{
+        CambioConfiguracion::PrecioCategoria(id, _) if self.categorias.get(id).is_none() => return Err(ClubError::CategoriaInvalida),
+        CambioConfiguracion::AsignarRol(_, Rol::Owner) => return Err(ClubError::RolInvalido),
+        CambioConfiguracion::Aprobadores(aprobadores, umbral) => Self::validar_aprobadores(aprobadores, *umbral)?,
+        CambioConfiguracion::Programado(valor, fecha_vigencia) => self.validar_programado(valor, *fecha_vigencia)?,
+        _ => {},
+      }
+      let id = self.cantidad_propuestas + 1;
+      let vencimiento = self.env().block_timestamp() + DIAS_PROPUESTA_CAMBIO * 24 * 60 * 60;
+      let mut propuesta = Propuesta{ id, cambio, proponente: caller, aprobaciones: [caller].to_vec(), vencimiento, ejecutada: false };
+      self.ejecutar_si_alcanza(&mut propuesta)?;
+      self.propuestas.insert(id, &propuesta);
+      self.cantidad_propuestas = id;
+      self.env().emit_event(CambioPropuesto{ id, proponente: caller, vencimiento });
+      if propuesta.ejecutada{
+        self.env().emit_event(CambioEjecutado{ id, caller });
+      }
+      Ok(id)
+    }
+    ///Un aprobador aprueba una propuesta de cambio de configuracion, y si se alcanzan las aprobaciones necesarias se ejecuta el cambio
+    ///Devuelve el error NoAutorizado si quien llama no es aprobador, PropuestaNoEncontrada si no existe, PropuestaEjecutada si ya se ejecuto,
+    ///PropuestaVencida si ya vencio, YaAprobada si quien llama ya la aprobo, o el error del cambio si no se pudo ejecutar
+    ///Ejemplo
+    ///'''
+    /// self.aprobar_cambio(1)?;
+    ///'''
+    #[ink(message)]
+    pub fn aprobar_cambio(&mut self, id: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let caller = self.env().caller();
+      if !self.aprobadores.contains(&caller){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut propuesta = self.propuestas.get(id).ok_or(ClubError::PropuestaNoEncontrada)?;
+      if propuesta.ejecutada{
+        return Err(ClubError::PropuestaEjecutada);
+      }
+      if self.env().block_timestamp() > propuesta.vencimiento{
+        return Err(ClubError::PropuestaVencida);
+      }
+      if propuesta.aprobaciones.contains(&caller){
+        return Err(ClubError::YaAprobada);
+      }
+      propuesta.aprobaciones.push(caller);
+      self.ejecutar_si_alcanza(&mut propuesta)?;
+      self.propuestas.insert(id, &propuesta);
+      let aprobaciones = propuesta.aprobaciones.len() as u32;
+      self.env().emit_event(CambioAprobado{ id, aprobador: caller, aprobaciones });
+      if propuesta.ejecutada{
+        self.env().emit_event(CambioEjecutado{ id, caller });
+      }
+      Ok(())
+    }
+    ///Si la propuesta tiene las aprobaciones necesarias, ejecuta el cambio y la marca como ejecutada
+    ///Solo cuentan las aprobaciones de quienes siguen siendo aprobadores
+    fn ejecutar_si_alcanza(&mut self, propuesta: &mut Propuesta)->Result<(), ClubError>{
+      let aprobaciones = propuesta.aprobaciones.iter().filter(|a| self.aprobadores.contains(a)).count();
+      if aprobaciones >= self.umbral_aprobacion as usize{
+        self.aplicar_cambio(propuesta.cambio.clone())?;
+        propuesta.ejecutada = true;
+      }
+      Ok(())
+    }
+    ///Aplica un cambio de configuracion aprobado
+    fn aplicar_cambio(&mut self, cambio: CambioConfiguracion)->Result<(), ClubError>{
+      match cambio{
+        CambioConfiguracion::Descuento(num) => {
+          self.descuento = num;
+          self.emitir_descuento_cambiado();
+        },
+        CambioConfiguracion::CantidadMeses(num) => {
+          self.cantidad_meses = num;
+          self.emitir_descuento_cambiado();
+        },
+        CambioConfiguracion::PrecioCategoria(id, precio) => {
+          if !self.categorias.contains(id){
+            return Err(ClubError::CategoriaInvalida);
+          }
+          let fecha = self.env().block_timestamp();
+          self.cambiar_precio(id, precio, fecha);
+        },
+        CambioConfiguracion::AsignarRol(clave, rol) => self.aplicar_asignar_rol(clave, rol)?,
+        CambioConfiguracion::QuitarRol(clave) => self.aplicar_quitar_rol(clave)?,
+        CambioConfiguracion::Aprobadores(aprobadores, umbral) => self.cambiar_aprobadores(aprobadores, umbral),
+        CambioConfiguracion::Programado(valor, fecha_vigencia) => {
+          self.agendar_cambio(valor, fecha_vigencia);
+        },
+      }
+      Ok(())
+    }
+    ///Devuelve la propuesta de cambio de configuracion con ese id, o None si no existe
+    ///Ejemplo
+    ///'''
+    /// let propuesta = self.get_propuesta(1);
+    ///'''
+    #[ink(message)]
+    pub fn get_propuesta(&self, id: u32)->Option<Propuesta>{
+      self.propuestas.get(id)
+    }
+    ///Calcula el id de un socio, que es el hash Blake2 de 128 bits de la sal secreta del club seguida del dni, leido como un numero en little endian
+    ///No es un mensaje del contrato: se usa fuera de la cadena, donde se guarda la sal, para que ni la sal ni los dni lleguen al contrato
+    ///Con la sal cualquiera puede probar todos los dni posibles (unos 10^8) y relacionar cada id con su dni, por eso no se tiene que publicar
+    ///Ejemplo
+    ///'''
+    /// let id = Club::calcular_id_socio(sal, 44851840);
+    ///'''
+    pub fn calcular_id_socio(sal: [u8; 32], dni: u128)->u128{
+      let mut salida = <Blake2x128 as HashOutput>::Type::default();
+      ink::env::hash_encoded::<Blake2x128, _>(&(sal, dni), &mut salida);
+      u128::from_le_bytes(salida)
+    }
+    ///Registra un nuevo socio en el club
+    ///La informacíon del socio se recibe por parametro
+    ///Se necesita saber el id del socio, que se calcula fuera de la cadena con calcular_id_socio a partir de su dni, la categoria y su actividad
+    ///Para registrarlo quien llama tiene que tener permiso de administracion, y se asegura que el socio a registrar no exista ya
+    ///crea el socio con su primer pago pendiente y lo pushea en el vector de socios del Club
+    ///Devuelve un error en caso que el socio ya exista, si la direccion que intenta registrar a un socio no está perimitida, si la categoria o la actividad no son correctas
+    ///o si la actividad no tiene mas cupo
+    ///Ejemplo
+    ///'''
+    /// self.registrar_socio(44851840, "B".to_string(), "FUTBOL".to_string())?;
+    ///'''
+    #[ink(message)]
+    pub fn registrar_socio(&mut self, id_socio: u128, cate: String, act: String)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.existe_socio(id_socio){
+        return Err(ClubError::SocioExistente);
+      }
+      //la categoria se busca por nombre en el catalogo, y tiene que estar activa
+      let categoria = self.buscar_categoria(&cate).filter(|c| c.activa).ok_or(ClubError::CategoriaInvalida)?;
+      let fecha_registro = self.env().block_timestamp();
+      let pagos_realizados:Vec<u128> = Vec::new();
+      //el primer pago pendiente va a tener el proximo id de pagos
+      let id_pago = self.cantidad_pagos + 1;
+      let pagos_pendientes:Vec<u128> = [id_pago].to_vec();
+      let pagos_sin_atrasos=0;
+      //solo se busca la actividad si la categoria deja elegirla
+      let actividad = match categoria.actividades{
+        ActividadesIncluidas::Cantidad(n) if n > 0 => Some(self.elegir_actividad(&act)?),
+        _ => None,
+      };
+      let socio:Socio=Socio::crear_socio(id_socio, &categoria, actividad.as_ref(), fecha_registro, pagos_realizados, pagos_pendientes, pagos_sin_atrasos)?;
+      let costo_mensual = self.calcular_cuota(&socio)?;
+      self.agregar_socio(&socio);
+      for a in &socio.actividades{
+        self.sumar_inscripto(*a);
+      }
+      let caller = self.env().caller();
+      self.env().emit_event(SocioRegistrado{ id_socio, caller, categoria: categoria.id });
+      self.crear_pago_pendiente(id_socio, costo_mensual, fecha_registro);
+      Ok(())
+    }
+    ///Se le debe pasar el id de la categoria para que calcule el precio de esa categoria y lo devuelve
+    ///Obtiene el precio mediante el catalogo de categorias del struct Club, aunque la categoria este retirada
+    ///Si hay un cambio de precio programado que ya rige pero todavia no se aplico, devuelve el precio de ese cambio
+    ///Devuelve el error CategoriaInvalida en caso de que no exista dicha categoria
+    ///Ejemplo
+    ///'''
+    /// let precio = self.calcular_precio(1)?;
+    ///'''
+    fn calcular_precio(&self, categoria: u32)->Result<u128, ClubError>{
+      let precio = self.categorias.get(categoria).map(|c| c.precio).ok_or(ClubError::CategoriaInvalida)?;
+      let programado = self.valor_vigente(|valor| match valor{
+        ValorProgramado::PrecioCategoria(id, precio) if *id == categoria => Some(*precio),
+        _ => None,
+      });
+      Ok(programado.unwrap_or(precio))
+    }
+    ///Devuelve el descuento que rige, teniendo en cuenta los cambios de descuento programados que ya rigen pero todavia no se aplicaron
+    fn descuento_vigente(&self)->u128{
+      self.valor_vigente(|valor| match valor{
+        ValorProgramado::Descuento(descuento) => Some(*descuento),
+        _ => None,
+      }).unwrap_or(self.descuento)
+    }
+    ///Busca entre los cambios programados que ya rigen el ultimo que cumple con el filtro, y devuelve su valor
+    fn valor_vigente(&self, filtro: impl Fn(&ValorProgramado)->Option<u128>)->Option<u128>{
+      let ahora = self.env().block_timestamp();
+      self.cambios_programados.iter()
+        .filter(|c| c.fecha_vigencia <= ahora)
+        .filter_map(|c| filtro(&c.valor).map(|v| ((c.fecha_vigencia, c.id), v)))
+        .max_by_key(|(orden, _)| *orden)
+        .map(|(_, v)| v)
+    }
+    ///Busca en el catalogo una categoria por su nombre y devuelve una copia, o "None" si no existe
+    ///Ejemplo
+    ///'''
+    /// let categoria:Option<Categoria> = self.buscar_categoria("A");
+    ///'''
+    fn buscar_categoria(&self, nombre: &str)->Option<Categoria>{
+      (1..=self.cantidad_categorias).filter_map(|id| self.categorias.get(id)).find(|c| c.nombre == nombre)
+    }
+    ///Crea el primer pago pendiente de cada socio cuando se registran y lo guarda en los pagos del club
+    ///Recibe el id del socio, el costo del pago, y la fecha de registro del socio para calcular la fecha de vencimiento
+    ///
+    ///Ejemplo
+    ///'''
+    /// self.crear_pago_pendiente(11444666, 5000, timestamp);
+    /// 
+    ///'''
+    fn crear_pago_pendiente(&mut self, id_socio: u128, costo: u128, fecha_registro: u64)->u128{
+    	let id = self.cantidad_pagos + 1;
+      let fecha_pago: Option<u64> = None;
+      let pagado=false;
+      let tiene_descuento=false;
+      let fecha_vencimiento = fecha_registro + (10 * 24 * 60 * 60); //le suma 10 dias a la fecha de registro
+      let pago:Pago=Pago::crear_pago(id_socio, id, costo, fecha_vencimiento, fecha_pago, pagado, tiene_descuento);
+      self.agregar_pago(&pago);
+      let caller = self.env().caller();
+      self.env().emit_event(PagoCreado{ id_socio, caller, id, costo, fecha_vencimiento });
+      id
+    }
+    ///Guarda un socio nuevo en el mapping de socios y le asigna la proxima posicion en el orden de registro
+    ///Ejemplo
+    ///'''
+    /// self.agregar_socio(&socio);
+    ///'''
+    fn agregar_socio(&mut self, socio: &Socio){
+      self.socios.insert(socio.id, socio);
+      self.orden_socios.insert(self.cantidad_socios, &socio.id);
+      self.cantidad_socios += 1;
+    }
+    ///Guarda un pago en el mapping de pagos y agrega su id a los pagos del socio
+    ///Si el id es mayor a la cantidad de pagos, la actualiza para que el proximo pago tenga un id nuevo
+    ///Ejemplo
+    ///'''
+    /// self.agregar_pago(&pago);
+    ///'''
+    fn agregar_pago(&mut self, pago: &Pago){
+      self.pagos.insert(pago.id, pago);
+      let mut ids = self.pagos_socio.get(pago.id_socio).unwrap_or_default();
+      ids.push(pago.id);
+      self.pagos_socio.insert(pago.id_socio, &ids);
+      if pago.id > self.cantidad_pagos{
+        self.cantidad_pagos = pago.id;
+      }
+    }
+    ///Vuelve a calcular la racha de pagos sin atraso del socio con sus pagos pagados, en el orden en que se crearon, por ejemplo despues de revertir uno
+    ///Igual que al registrar cada pago, uno pagado a tiempo suma uno y uno pagado vencido vuelve la racha a 0,
+    ///y un pago creado con descuento marca desde donde se cuenta el proximo descuento
+    ///Ejemplo
+    ///'''
+    /// self.recalcular_racha(&mut socio);
+    ///'''
+    fn recalcular_racha(&self, socio: &mut Socio){
+      socio.pagos_sin_atrasos = 0;
+      socio.racha_descuento = 0;
+      for pago in self.pagos_socio.get(socio.id).unwrap_or_default().iter().filter_map(|id| self.pagos.get(id)){
+        if pago.tiene_descuento{
+          socio.racha_descuento = socio.pagos_sin_atrasos;
+        }
+        if pago.pagado{
+          socio.registrar_puntualidad(&pago);
+        }
+      }
+    }
+    ///Devuelve los pagos de un socio en el orden en que se crearon, usando los ids guardados para ese socio
+    ///Si el socio no tiene pagos devuelve un vector vacio
+    ///Ejemplo
+    ///'''
+    /// let pagos:Vec<Pago> = self.pagos_de_socio(44851840);
+    ///'''
+    fn pagos_de_socio(&self, id_socio: u128)->Vec<Pago>{
+      self.pagos_socio.get(id_socio).unwrap_or_default().iter().filter_map(|id| self.pagos.get(id)).collect()
+    }
+    ///Nos busca en el mapping de socios del struct club el socio
+    ///Le mandamos por parametro el id correspondiente y a traves de ese dato, busca si existe un socio con el mismo id
+    ///Si lo encuentra devuelve una copia del socio, en caso contrario devuleve "None" indicando que no se encontro ningun socio con ese id
+    ///Ejemplo
+    ///'''
+    ///   let ok:bool;
+    ///		let s:Option<Socio>=self.buscar_socio(44851840);
+    ///		let Some(socio)=s{
+    ///			ok=true;
+  	///		}	else{ok=false;} 
+    ///	'''
+    fn buscar_socio(&self,id_socio:u128)->Option<Socio>{
+      self.socios.get(id_socio)
+    }
+    ///Nos devulve la informacion del primer pago pendiente que tenga un determinado socio
+    ///Le pasamos el socio que queremos, ya buscado por su id
+    ///Si el socio tiene pagos pendientes, nos devuelve una copia de la informacion del primer pago pendiente dentro del vector de "pagos_pendientes" del struct de socio
+    ///si no tiene pagos pendientes, devuleve un "None" indicando que no tiene pagos pendientes el socio indicado
+    /// Ejemplo
+    /// '''
+    ///   let ok:bool;
+    ///   let pagos:Option<u128>=self.primero_pagos_pendientes(&socio);
+    ///   if let Some(p)=pagos{
+    ///     ok=true;
+    ///   }else{
+    ///     ok=false;
+    ///   }
+    ///'''
+    fn primero_pagos_pendientes(&self, socio: &Socio)->Option<u128>{
+      socio.pagos_pendientes.first().copied()
+    }
+    /// Esta funcion nos registra un pago de un socio determinado, le tenemos que pasar el id y transferir el monto del pago junto con la llamada.
+    /// Si el socio existe, se fija si tiene pagos pendientes. Si tiene pagos pendientes, se fija si no fue pagada y cuanto le falta pagar.
+    /// Si lo transferido no alcanza, se registra como un pago parcial y el pago sigue pendiente hasta que se termine de pagar
+    /// Si alcanza, lo saco de la informacion de pagos pendientes del socio y cambio la informacion asociada al vector de pagos del correspodniente socio,por ultimo lo agrego al vector de los pagos realizados del socio.
+    /// Si el pago esta vencido, ademas del costo se tiene que pagar el recargo por atraso, que queda guardado en el pago
+    /// Si el pago se hizo a tiempo suma uno a la racha de pagos sin atraso del socio, y si se hizo vencido la racha vuelve a 0
+    /// Todo lo transferido se suma a la tesoreria del club, y si se transfirio de mas el excedente queda como saldo a favor del socio para sus proximos pagos
+    /// El excedente es siempre del socio, aunque lo transfiera un cajero u otra cuenta en su nombre: no se le devuelve a quien pago,
+    /// y quien lo transfirio queda como caller en el evento SaldoAcreditado
+    /// Si no cumple alguna de las anteriores condiciones, nos devuelve su correspondiente error y se revierte la transferencia
+    /// Por Ejemplo si no tiene pagos pendientes el socio, nos devuelve el error SinPagosPendientes, y si no se transfirio nada MontoIncorrecto
+    /// Ejemplo
+    ///'''
+    /// self.registro_pago(44851840)?; //transfiriendo 5000
+    ///
+    ///'''
+    #[ink(message, payable)]
+    pub fn registro_pago(&mut self,id_socio:u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let monto = self.env().transferred_value();
+      if !self.tiene_permiso(Permiso::RegistrarPagos){
+        return Err(ClubError::NoAutorizado);
+      }
+      self.aplicar_pago(id_socio, monto)
+    }
+    ///Aplica el monto transferido al proximo pago pendiente del socio, sin fijarse quien llama
+    ///Lo usan registro_pago y pagar_mi_cuota, y devuelve los mismos errores que registro_pago salvo NoAutorizado
+    ///Lo que sobra se le acredita al socio del pago, sin importar quien llama
+    ///Ejemplo
+    ///'''
+    /// self.aplicar_pago(44851840, 5000)?;
+    ///'''
+    fn aplicar_pago(&mut self, id_socio: u128, monto: Balance)->Result<(), ClubError>{
+      //busco el socio 
+      let mut socio=self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      //si tiene pagos pendientes, accedo a la primera posicion de pagos pendientes obteniendo el id del pago
+      let id=self.primero_pagos_pendientes(&socio).ok_or(ClubError::SinPagosPendientes)?;
+      let mut pago=self.pagos.get(id).ok_or(ClubError::SinPagosPendientes)?;
+      //si ya fue pagada no hay nada pendiente
+      if pago.pagado{
+        return Err(ClubError::SinPagosPendientes);
+      }
+      //lo que le falta pagar, con el recargo si esta vencido
+      let fecha_hoy = self.env().block_timestamp();
+      let recargo = self.calcular_recargo(&pago, fecha_hoy);
+      let restante = pago.saldo(recargo);
+      if monto == 0 && restante > 0{
+        return Err(ClubError::MontoIncorrecto);
+      }
+      let aplicado = monto.min(restante);
+      pago.monto_pagado += aplicado;
+      let caller = self.env().caller();
+      self.registrar_aporte(id, caller, aplicado);
+      if aplicado == restante{
+        //lo saco de la informacion de pagos pendientes del socio
+        socio.pagos_pendientes.remove(0);
+        //cambio el pago guardado con ese id
+        pago.pagado=true;
+        pago.fecha_pago=Some(fecha_hoy);
+        pago.recargo=recargo;
+        pago.pagador=Some(caller);
+        self.pagos.insert(id, &pago);
+        //lo agrego a pagos realizados del socio y actualizo su racha de pagos sin atraso
+        socio.pagos_realizados.push(id);
+        socio.registrar_puntualidad(&pago);
+        self.socios.insert(id_socio, &socio);
+        self.env().emit_event(PagoRegistrado{ id_socio, caller, id, monto: pago.monto_pagado });
+      }else{
+        //pago parcial, el pago sigue pendiente
+        self.pagos.insert(id, &pago);
+        self.env().emit_event(PagoParcialRegistrado{ id_socio, caller, id, monto: aplicado, restante: restante - aplicado });
+      }
+      //lo transferido entra a la tesoreria y lo que sobra queda a favor del socio
+      self.tesoreria += monto;
+      self.acreditar(id_socio, monto - aplicado, String::from("excedente de pago"));
+      Ok(())
+    }
+    ///Un socio paga su propia cuota desde la cuenta vinculada, transfiriendo el monto junto con la llamada
+    ///Funciona igual que registro_pago, pero no necesita un rol y solo puede pagar los pagos de su propio socio
+    ///Devuelve el error CuentaNoVinculada si quien llama no tiene un socio vinculado, o los mismos errores que registro_pago
+    ///Ejemplo
+    ///'''
+    /// self.pagar_mi_cuota()?; //transfiriendo 5000
+    ///'''
+    #[ink(message, payable)]
+    pub fn pagar_mi_cuota(&mut self)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let monto = self.env().transferred_value();
+      let id_socio = self.socio_de_cuenta.get(self.env().caller()).ok_or(ClubError::CuentaNoVinculada)?;
+      self.aplicar_pago(id_socio, monto)
+    }
+    ///Un socio consulta su propio estado de cuenta desde la cuenta vinculada, sin necesitar un rol
+    ///Devuelve el error CuentaNoVinculada si quien llama no tiene un socio vinculado
+    ///Ejemplo
+    ///'''
+    /// let estado = self.mi_estado_de_cuenta()?;
+    ///'''
+    #[ink(message)]
+    pub fn mi_estado_de_cuenta(&self)->Result<EstadoCuenta, ClubError>{
+      let id_socio = self.socio_de_cuenta.get(self.env().caller()).ok_or(ClubError::CuentaNoVinculada)?;
+      let socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      Ok(EstadoCuenta{
+        id_socio,
+        categoria: self.nombre_categoria(socio.categoria)?,
+        estado: socio.estado,
+        pagos: self.resumen_pagos(id_socio),
+        saldo_a_favor: self.saldos_a_favor.get(id_socio).unwrap_or(0),
+        monto_a_pagar: self.monto_a_pagar(id_socio).ok(),
+      })
+    }
+    ///Quien llama pide vincular su cuenta a un socio, y el vinculo queda pendiente hasta que lo apruebe alguien con permiso de administracion
+    ///Si ya habia pedido vincularse a otro socio, el pedido se reemplaza
+    ///Devuelve el error SocioNoEncontrado si no existe el socio, SocioYaVinculado si el socio ya tiene una cuenta o CuentaYaVinculada si quien llama ya tiene un socio
+    ///Ejemplo
+    ///'''
+    /// self.solicitar_vinculo(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn solicitar_vinculo(&mut self, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let cuenta = self.env().caller();
+      self.validar_vinculo(id_socio, cuenta)?;
+      self.vinculos_pendientes.insert(cuenta, &id_socio);
+      self.env().emit_event(VinculoSolicitado{ id_socio, cuenta });
+      Ok(())
+    }
+    ///Se fija que exista el socio y que ni el socio ni la cuenta esten vinculados
+    fn validar_vinculo(&self, id_socio: u128, cuenta: AccountId)->Result<(), ClubError>{
+      if !self.existe_socio(id_socio){
+        return Err(ClubError::SocioNoEncontrado);
+      }
+      if self.cuenta_de_socio.contains(id_socio){
+        return Err(ClubError::SocioYaVinculado);
+      }
+      if self.socio_de_cuenta.contains(cuenta){
+        return Err(ClubError::CuentaYaVinculada);
+      }
+      Ok(())
+    }
+    ///Aprueba el vinculo que pidio una cuenta, y desde ese momento la cuenta puede pagar y consultar los datos de su socio
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, SinVinculoPendiente si la cuenta no pidio vincularse,
+    ///o SocioYaVinculado o CuentaYaVinculada si mientras tanto se vinculo otra cuenta o socio
+    ///Ejemplo
+    ///'''
+    /// self.aprobar_vinculo(account_id)?;
+    ///'''
+    #[ink(message)]
+    pub fn aprobar_vinculo(&mut self, cuenta: AccountId)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let id_socio = self.vinculos_pendientes.get(cuenta).ok_or(ClubError::SinVinculoPendiente)?;
+      self.validar_vinculo(id_socio, cuenta)?;
+      self.vinculos_pendientes.remove(cuenta);
+      self.cuenta_de_socio.insert(id_socio, &cuenta);
+      self.socio_de_cuenta.insert(cuenta, &id_socio);
+      let caller = self.env().caller();
+      self.env().emit_event(CuentaVinculada{ id_socio, cuenta, caller });
+      Ok(())
+    }
+    ///Desvincula la cuenta de un socio, lo puede hacer alguien con permiso de administracion o la misma cuenta vinculada
+    ///Devuelve el error NoAutorizado si quien llama no puede, o CuentaNoVinculada si el socio no tiene una cuenta vinculada
+    ///Ejemplo
+    ///'''
+    /// self.desvincular_cuenta(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn desvincular_cuenta(&mut self, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let caller = self.env().caller();
+      let cuenta = self.cuenta_de_socio.get(id_socio);
+      if !self.tiene_permiso(Permiso::Administrar) && cuenta != Some(caller){
+        return Err(ClubError::NoAutorizado);
+      }
+      let cuenta = cuenta.ok_or(ClubError::CuentaNoVinculada)?;
+      self.cuenta_de_socio.remove(id_socio);
+      self.socio_de_cuenta.remove(cuenta);
+      self.env().emit_event(CuentaDesvinculada{ id_socio, cuenta, caller });
+      Ok(())
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve la cuenta vinculada a un socio, o None si no tiene
+    ///Devuelve el error NoAutorizado si no tiene permiso, o SocioNoEncontrado si no existe el socio
+    ///Ejemplo
+    ///'''
+    /// let cuenta = self.get_cuenta_socio(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_cuenta_socio(&self, id_socio: u128)->Result<Option<AccountId>, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.existe_socio(id_socio){
+        return Err(ClubError::SocioNoEncontrado);
+      }
+      Ok(self.cuenta_de_socio.get(id_socio))
+    }
+    ///Suma a lo que transfirio una cuenta para un pago, que es lo que se le puede reembolsar si el pago se revierte
+    ///Si el monto es 0 no hace nada
+    ///Ejemplo
+    ///'''
+    /// self.registrar_aporte(12, caller, 2000);
+    ///'''
+    fn registrar_aporte(&mut self, id_pago: u128, cuenta: AccountId, monto: Balance){
+      if monto > 0{
+        let mut aportes = self.aportes_pago.get(id_pago).unwrap_or_default();
+        match aportes.iter_mut().find(|(c, _)| *c == cuenta){
+          Some(aporte) => aporte.1 += monto,
+          None => aportes.push((cuenta, monto)),
+        }
+        self.aportes_pago.insert(id_pago, &aportes);
+      }
+    }
+    ///Le transfiere un monto a una cuenta, por ejemplo lo que le sobro a un socio que se da de baja
+    ///Si el monto es 0 no hace nada, y si la transferencia falla lo guarda como reembolso pendiente de esa cuenta para que lo reclame despues
+    ///Ejemplo
+    ///'''
+    /// self.devolver(cuenta, 500);
+    ///'''
+    fn devolver(&mut self, cuenta: AccountId, monto: Balance){
+      if monto > 0 && self.env().transfer(cuenta, monto).is_err(){
+        let pendiente = self.reembolsos_pendientes.get(cuenta).unwrap_or(0);
+        self.reembolsos_pendientes.insert(cuenta, &(pendiente + monto));
+        self.total_reembolsos_pendientes += monto;
+      }
+    }
+    ///Le transfiere a quien llama el reembolso que tenia pendiente y lo devuelve
+    ///Devuelve el error SinReembolsosPendientes si no tiene reembolsos pendientes o TransferenciaFallida si no se pudo hacer la transferencia
+    ///Ejemplo
+    ///'''
+    /// let reembolso = self.reclamar_reembolso()?;
+    ///'''
+    #[ink(message)]
+    pub fn reclamar_reembolso(&mut self)->Result<Balance, ClubError>{
+      self.verificar_pausa()?;
+      let caller = self.env().caller();
+      let pendiente = self.reembolsos_pendientes.get(caller).unwrap_or(0);
+      if pendiente == 0{
+        return Err(ClubError::SinReembolsosPendientes);
+      }
+      if self.env().transfer(caller, pendiente).is_err(){
+        return Err(ClubError::TransferenciaFallida);
+      }
+      self.reembolsos_pendientes.remove(caller);
+      self.total_reembolsos_pendientes -= pendiente;
+      self.env().emit_event(ReembolsoReclamado{ caller, monto: pendiente });
+      Ok(pendiente)
+    }
+    ///Suma un monto al saldo a favor de un socio, guardando el motivo en el evento
+    ///Ejemplo
+    ///'''
+    /// self.acreditar(44851840, 500, "excedente de pago".to_string());
+    ///'''
+    fn acreditar(&mut self, id_socio: u128, monto: Balance, motivo: String){
+      if monto > 0{
+        let saldo = self.saldos_a_favor.get(id_socio).unwrap_or(0);
+        self.saldos_a_favor.insert(id_socio, &(saldo + monto));
+        let caller = self.env().caller();
+        self.env().emit_event(SaldoAcreditado{ id_socio, caller, monto, motivo });
+      }
+    }
+    ///Le acredita un saldo a favor a un socio, con el motivo, solo si el que llama es el owner
+    ///El saldo se descuenta del proximo pago que se le cree al socio
+    ///Devuelve el error NoAutorizado si no es el owner, SocioNoEncontrado si no existe el socio o MontoIncorrecto si el monto es 0
+    ///Ejemplo
+    ///'''
+    /// self.acreditar_saldo(44851840, 1000, "bonificacion por torneo".to_string())?;
+    ///'''
+    #[ink(message)]
+    pub fn acreditar_saldo(&mut self, id_socio: u128, monto: Balance, motivo: String)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.existe_socio(id_socio){
+        return Err(ClubError::SocioNoEncontrado);
+      }
+      if monto == 0{
+        return Err(ClubError::MontoIncorrecto);
+      }
+      self.acreditar(id_socio, monto, motivo);
+      Ok(())
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve el saldo a favor que tiene un socio
+    ///Devuelve el error NoAutorizado si no tiene permiso, o SocioNoEncontrado si no existe el socio
+    ///Ejemplo
+    ///'''
+    /// let saldo = self.get_saldo_a_favor(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_saldo_a_favor(&self, id_socio: u128)->Result<Balance, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.existe_socio(id_socio){
+        return Err(ClubError::SocioNoEncontrado);
+      }
+      Ok(self.saldos_a_favor.get(id_socio).unwrap_or(0))
+    }
+    ///Revierte un pago ya pagado, por ejemplo si se registro con el socio equivocado, y deja una nota de credito con el motivo
+    ///El pago vuelve a quedar pendiente para el socio, sin lo que se habia pagado ni el recargo cobrado, y su racha de pagos sin atraso se vuelve a calcular sin el
+    ///Si "reembolsar" es true, a la direccion que termino de pagarlo se le devuelve lo que ella transfirio para ese pago, que sale de la tesoreria, y solo lo puede hacer el owner
+    ///El resto de lo pagado, como lo que se cubrio con el saldo a favor o lo que transfirieron otras direcciones, queda en la tesoreria y se le acredita al socio como saldo a favor
+    ///Los pagos de antes de que se guardara lo transferido por cada direccion no tienen nada para reembolsar, y todo se acredita como saldo a favor
+    ///Devuelve el id de la nota de credito, o el error NoAutorizado, PagoNoEncontrado, PagoNoPagado si el pago no estaba pagado,
+    ///SocioInactivo si el socio se dio de baja, FondosInsuficientes o TransferenciaFallida
+    ///Ejemplo
+    ///'''
+    /// let nota = self.revertir_pago(12, "socio equivocado".to_string(), true)?;
+    ///'''
+    #[ink(message)]
+    pub fn revertir_pago(&mut self, id: u128, motivo: String, reembolsar: bool)->Result<u128, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar) || (reembolsar && !self.es_owner()){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut pago = self.pagos.get(id).ok_or(ClubError::PagoNoEncontrado)?;
+      if !pago.pagado{
+        return Err(ClubError::PagoNoPagado);
+      }
+      let mut socio = self.buscar_socio(pago.id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado == EstadoSocio::DadoDeBaja{
+        return Err(ClubError::SocioInactivo);
+      }
+      let monto = pago.monto_pagado;
+      //solo se reembolsa lo que transfirio el pagador, sin pasarse de lo pagado
+      let pagador = if reembolsar { pago.pagador } else { None };
+      let reembolso = pagador.map(|pagador| {
+        let aportado: Balance = self.aportes_pago.get(id).unwrap_or_default().iter().filter(|(c, _)| *c == pagador).map(|(_, m)| m).sum();
+        (pagador, aportado.min(monto))
+      }).filter(|(_, efectivo)| *efectivo > 0);
+      if let Some((pagador, efectivo)) = reembolso{
+        if efectivo > self.fondos_disponibles(){
+          return Err(ClubError::FondosInsuficientes);
+        }
+        if self.env().transfer(pagador, efectivo).is_err(){
+          return Err(ClubError::TransferenciaFallida);
+        }
+        self.tesoreria -= efectivo;
+      }
+      let reembolsado = reembolso.map_or(0, |(_, efectivo)| efectivo);
+      self.aportes_pago.remove(id);
+      //el pago vuelve a los pendientes, ordenado por id
+      socio.pagos_realizados.retain(|p| *p != id);
+      let posicion = socio.pagos_pendientes.iter().position(|p| *p > id).unwrap_or(socio.pagos_pendientes.len());
+      socio.pagos_pendientes.insert(posicion, id);
+      pago.pagado = false;
+      pago.fecha_pago = None;
+      pago.monto_pagado = 0;
+      pago.recargo = 0;
+      pago.pagador = None;
+      self.pagos.insert(id, &pago);
+      self.recalcular_racha(&mut socio);
+      //se guarda la nota de credito, que no se puede modificar
+      let caller = self.env().caller();
+      let nota = NotaCredito{
+        id: self.cantidad_notas_credito + 1,
+        id_pago: id,
+        id_socio: pago.id_socio,
+        monto,
+        motivo: motivo.clone(),
+        fecha: self.env().block_timestamp(),
+        reembolsado: reembolso.is_some(),
+        caller,
+      };
+      self.notas_credito.insert(nota.id, &nota);
+      self.cantidad_notas_credito = nota.id;
+      let mut notas = self.notas_pago.get(id).unwrap_or_default();
+      notas.push(nota.id);
+      self.notas_pago.insert(id, &notas);
+      self.socios.insert(socio.id, &socio);
+      self.env().emit_event(PagoRevertido{ id_socio: socio.id, caller, id_pago: id, nota: nota.id, monto, reembolsado: nota.reembolsado });
+      self.acreditar(socio.id, monto - reembolsado, motivo);
+      Ok(nota.id)
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve las notas de credito de un pago, de la mas vieja a la mas nueva
+    ///Devuelve el error NoAutorizado si no tiene permiso, o PagoNoEncontrado si no existe el pago
+    ///Ejemplo
+    ///'''
+    /// let notas = self.get_notas_credito(12)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_notas_credito(&self, id_pago: u128)->Result<Vec<NotaCredito>, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.pagos.contains(id_pago){
+        return Err(ClubError::PagoNoEncontrado);
+      }
+      let ids = self.notas_pago.get(id_pago).unwrap_or_default();
+      Ok(ids.iter().filter_map(|id| self.notas_credito.get(id)).collect())
+    }
+    ///Devuelve el total de fondos que el club recibio por los pagos y todavia no retiro
+    ///Ejemplo
+    ///'''
+    /// let saldo = self.balance_tesoreria();
+    ///'''
+    #[ink(message)]
+    pub fn balance_tesoreria(&self)->Balance{
+      self.tesoreria
+    }
+    ///Calcula y devuelve cuanto se puede retirar de la tesoreria
+    ///Es lo recaudado, pero sin tocar los fondos del contrato que se reservan para los reembolsos pendientes
+    ///Ejemplo
+    ///'''
+    /// let disponible = self.fondos_disponibles();
+    ///'''
+    #[ink(message)]
+    pub fn fondos_disponibles(&self)->Balance{
+      let balance_libre = self.env().balance().saturating_sub(self.total_reembolsos_pendientes);
+      if self.tesoreria < balance_libre{
+        self.tesoreria
+      }else{
+        balance_libre
+      }
+    }
+    ///Retira fondos de la tesoreria enviandolos a la direccion destino, solo si el que llama es el owner
+    ///El monto no puede superar los fondos disponibles, el retiro queda registrado con la fecha, quien lo hizo y el destino
+    ///Devuelve un error si no es el owner, si no hay fondos suficientes o si falla la transferencia
+    ///Ejemplo
+    ///'''
+    /// self.retirar_fondos(5000, account_id)?;
+    ///'''
+    #[ink(message)]
+    pub fn retirar_fondos(&mut self, monto: Balance, destino: AccountId)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
+      }
+      if monto > self.fondos_disponibles(){
+        return Err(ClubError::FondosInsuficientes);
+      }
+      if self.env().transfer(destino, monto).is_err(){
+        return Err(ClubError::TransferenciaFallida);
+      }
+      self.tesoreria -= monto;
+      let caller = self.env().caller();
+      let retiro = Retiro{
+        monto,
+        fecha: self.env().block_timestamp(),
+        caller,
+        destino,
+      };
+      self.retiros.push(retiro);
+      self.env().emit_event(FondosRetirados{ caller, destino, monto });
+      Ok(())
+    }
+    ///Devuelve el listado de los retiros que se hicieron de la tesoreria, si quien llama no tiene permiso de consulta devuelve un vector vacio
+    ///Ejemplo
+    ///'''
+    /// let retiros = self.get_retiros();
+    ///'''
+    #[ink(message)]
+    pub fn get_retiros(&self)->Vec<Retiro>{
+      let mut vec:Vec<Retiro> = Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        vec = self.retiros.clone();
+      }
+      vec
+    }
+    ///Esta funcion nos devuelve la informacion de un socio determinado, nos indica su id, categoria y los pagos realizados
+    ///Le debemos pasar el id, si le pasamos un id="None" nos devulve un id y categoria con valor "None" y un listado de los costos de los ultimos 30 pagos realizados.
+    ///En caso contrario, si le pasamos un id valido, lo busca y si encuntra el socio devuleve el id y categoria correspondiente, con el listado de los costos de los pagos realizados.
+    ///Si no encuntra el socio, devuelve el error SocioNoEncontrado
+    /// Ejemplo
+    ///'''
+    /// let a:Adicional=self.consultar_pagos(Some(44851840))?;
+    /// assert_eq!(a.id_socio,44851840);
+    ///'''
+    
+    #[ink(message)]
+    pub fn consultar_pagos(&self,id_socio:Option<u128>)->Result<Adicional, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let p ;
+      let mut encontrado=None;
+      let mut cate=None;
+      if let Some(id)=id_socio{
+        encontrado = Some(id);
+        let socio=self.buscar_socio(id).ok_or(ClubError::SocioNoEncontrado)?;
+        cate=Some(self.nombre_categoria(socio.categoria)?);
+        p=self.pagos_de_socio(id).iter().map(|pago| pago.costo).collect();
+      } else {
+       //los ultimos 30 pagos son los de id mas alto
+       p= (1..=self.cantidad_pagos).rev().take(30).filter_map(|id| self.pagos.get(id)).map(|pago| pago.costo).collect();
+      }
+      Ok(Adicional{
+        id_socio: encontrado,
+        cate,
+        p,
+      })
+    }
+    ///Recibe un monto, calcula el % de descuento de dicho monto y lo retorna, si no lo pudo hacer, devuelve un None
+    ///Ejemplo
+    ///'''
+    /// let porciento_del_descuento = self.descuento_otorgado(5000);
+    ///
+    ///'''
+    fn descuento_otorgado (&self,monto:u128)->Option<u128>{
+      let i=monto.checked_mul(self.descuento_vigente());
+      if let Some(a)=i{
+        let s=a.checked_div(100);//le aplico un 30% de descuento 
+        if let Some(d)=s{
+          return Some(d);
+        }
+      }
+      None
+    }
+    
+    ///Nos cambia el descuento que se le otorga a los socios cuando pagan una cantidad determinada de veces consecutivas sin atrasos
+    ///Le pasamos el descuento nuevo que otorgamos
+    ///Devuelve el error ContratoEnPausa, NoAutorizado si quien llama no tiene permiso, o RequiereAprobacion si la aprobacion multiple esta activa
+    ///y el cambio se tiene que proponer con proponer_cambio
+    /// Ejemplo
+    /// '''
+    ///   self.set_descuento(5000)?;
+    ///   assert_eq!(self.descuento,5000);
+    /// '''
+    #[ink(message)]
+    pub fn set_descuento(&mut self, num: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      self.descuento = num;
+      self.emitir_descuento_cambiado();
+      Ok(())
+    }
+    
+    ///Nos cambia la cantidad de meses que el socio deberia pagar sin atrasos para obtener el descuento.
+    ///Debemos pasarle el numero que queremos cambiar
+    ///Devuelve el error ContratoEnPausa, NoAutorizado si quien llama no tiene permiso, o RequiereAprobacion si la aprobacion multiple esta activa
+    ///y el cambio se tiene que proponer con proponer_cambio
+    ///Ejemplo
+    /// '''
+    ///   self.set_cantidad_meses(5)?;
+    ///   assert_eq!(self.cantidad_meses,5);
+    /// '''
+    #[ink(message)]
+    pub fn set_cantidad_meses(&mut self, num: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      self.cantidad_meses = num;
+      self.emitir_descuento_cambiado();
+      Ok(())
+    }
+    ///Cambia los recargos que se cobran por pagar un pago vencido
+    ///Recibe el recargo fijo, los dias despues del vencimiento a partir de los cuales se cobra, y el interes por mil que se cobra por cada dia de atraso
+    ///Con los valores en 0 no se cobra ningun recargo
+    ///Devuelve el error ContratoEnPausa, o NoAutorizado si quien llama no tiene permiso
+    ///Ejemplo
+    /// '''
+    ///   self.set_recargos(500, 5, 2)?; //500 pasados 5 dias, mas 0,2% del costo por dia
+    /// '''
+    #[ink(message)]
+    pub fn set_recargos(&mut self, recargo_fijo: u128, dias_recargo: u64, interes_diario: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      self.recargo_fijo = recargo_fijo;
+      self.dias_recargo = dias_recargo;
+      self.interes_diario = interes_diario;
+      let caller = self.env().caller();
+      self.env().emit_event(RecargosCambiados{ caller, recargo_fijo, dias_recargo, interes_diario });
+      Ok(())
+    }
+    ///Calcula el recargo que le corresponde a un pago si se paga en la fecha recibida
+    ///Si no esta vencido es 0, si no es el interes diario por los dias de atraso, mas el recargo fijo si ya pasaron los dias del recargo
+    ///Ejemplo
+    ///'''
+    /// let recargo = self.calcular_recargo(&pago, self.env().block_timestamp());
+    ///'''
+    fn calcular_recargo(&self, pago: &Pago, fecha: u64)->u128{
+      if fecha <= pago.fecha_vencimiento{
+        return 0;
+      }
+      let dias = pago.dias_de_atraso(fecha) as u128;
+      let mut recargo = pago.costo.saturating_mul(self.interes_diario).saturating_mul(dias) / 1000;
+      if dias >= self.dias_recargo as u128{
+        recargo = recargo.saturating_add(self.recargo_fijo);
+      }
+      recargo
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve lo que falta pagar del costo y del recargo por atraso del proximo pago pendiente de un socio
+    ///que sumados es lo que tiene que transferir al llamar a registro_pago en este momento para saldarlo. Los pagos parciales se descuentan primero del costo
+    ///Devuelve el error NoAutorizado si no tiene permiso, SocioNoEncontrado si no existe el socio o SinPagosPendientes si no debe nada
+    ///Ejemplo
+    ///'''
+    /// let (costo, recargo) = self.get_monto_a_pagar(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_monto_a_pagar(&self, id_socio: u128)->Result<(u128, u128), ClubError>{
+      if !self.tiene_permiso(Permiso::RegistrarPagos){
+        return Err(ClubError::NoAutorizado);
+      }
+      self.monto_a_pagar(id_socio)
+    }
+    ///Calcula lo que falta pagar del costo y del recargo del proximo pago pendiente de un socio, sin fijarse quien llama
+    fn monto_a_pagar(&self, id_socio: u128)->Result<(u128, u128), ClubError>{
+      let socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      let id = self.primero_pagos_pendientes(&socio).ok_or(ClubError::SinPagosPendientes)?;
+      let pago = self.pagos.get(id).filter(|p| !p.pagado).ok_or(ClubError::SinPagosPendientes)?;
+      let recargo = self.calcular_recargo(&pago, self.env().block_timestamp());
+      let falta_costo = pago.costo.saturating_sub(pago.monto_pagado);
+      Ok((falta_costo, pago.saldo(recargo) - falta_costo))
+    }
+    ///Arma un plan de cuotas para la deuda de un socio, que es lo que le falta pagar de todos sus pagos pendientes mas los recargos al dia de hoy
+    ///Los pagos pendientes quedan marcados como refinanciados y dejan de estar pendientes, y la deuda se divide en "cuotas" pagos nuevos
+    ///La primera cuota vence "dias_entre_cuotas" dias despues de hoy y cada una de las siguientes la misma cantidad de dias despues de la anterior
+    ///Si la deuda no se divide exacto, la ultima cuota lleva el resto. Las cuotas se pagan con registro_pago como cualquier otro pago
+    ///Devuelve los ids de las cuotas, o el error NoAutorizado, SocioNoEncontrado, CuotasInvalidas si las cuotas o los dias son 0 o si hay mas cuotas que deuda,
+    ///porque alguna cuota quedaria en 0, o SinPagosPendientes si no debe nada
+    ///Ejemplo
+    ///'''
+    /// let cuotas = self.crear_plan_cuotas(44851840, 3, 30)?;
+    ///'''
+    #[ink(message)]
+    pub fn crear_plan_cuotas(&mut self, id_socio: u128, cuotas: u32, dias_entre_cuotas: u64)->Result<Vec<u128>, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if cuotas == 0 || dias_entre_cuotas == 0{
+        return Err(ClubError::CuotasInvalidas);
+      }
+      let fecha_hoy = self.env().block_timestamp();
+      let pendientes:Vec<Pago> = socio.pagos_pendientes.iter().filter_map(|id| self.pagos.get(id)).filter(|pago| !pago.pagado).collect();
+      let deuda:u128 = pendientes.iter().map(|pago| pago.saldo(self.calcular_recargo(pago, fecha_hoy))).sum();
+      if deuda == 0{
+        return Err(ClubError::SinPagosPendientes);
+      }
+      if cuotas as u128 > deuda{
+        return Err(ClubError::CuotasInvalidas);
+      }
+      //los pagos que se deben pasan al plan
+      for mut pago in pendientes{
+        pago.refinanciado = true;
+        self.pagos.insert(pago.id, &pago);
+      }
+      socio.pagos_pendientes.clear();
+      let caller = self.env().caller();
+      let valor_cuota = deuda / cuotas as u128;
+      let mut ids:Vec<u128> = Vec::new();
+      for k in 1..=cuotas{
+        let id = self.cantidad_pagos + 1;
+        let costo = if k == cuotas { deuda - valor_cuota * (cuotas as u128 - 1) } else { valor_cuota };
+        let fecha_vencimiento = fecha_hoy + (k as u64) * dias_entre_cuotas * 24 * 60 * 60;
+        let mut pago = Pago::crear_pago(id_socio, id, costo, fecha_vencimiento, None, false, false);
+        pago.cuota_plan = true;
+        self.agregar_pago(&pago);
+        socio.pagos_pendientes.push(id);
+        ids.push(id);
+        self.env().emit_event(PagoCreado{ id_socio, caller, id, costo, fecha_vencimiento });
+      }
+      self.socios.insert(id_socio, &socio);
+      self.env().emit_event(PlanCuotasCreado{ id_socio, caller, deuda, cuotas: ids.clone() });
+      Ok(ids)
+    }
+    ///Emite el evento con la configuracion actual del descuento y la cantidad de meses para obtenerlo
+    fn emitir_descuento_cambiado(&self){
+      let caller = self.env().caller();
+      self.env().emit_event(DescuentoCambiado{ caller, descuento: self.descuento, cantidad_meses: self.cantidad_meses });
+    }
+    ///Crea un nuevo pago y lo guarda en los pagos del club, solo si quien llama tiene permiso de administracion y existe el socio
+    ///Recibe como parametro el id del socio
+    ///Busca al socio para obtener los datos restantes
+    ///Calcula la fecha de vencimiento del pago mediante la fecha de vencimiento del pago anterior
+    ///Tambien se fija si el pago va a tener descuento o no, para saber eso se fija si la cantidad de meses sin pagos atrasados consecutivos es la que pide el club
+    ///Antes de crear el pago aplica los cambios de precio y de descuento programados que ya rigen
+    ///Devuelve un error si no esta permitido, si el socio no existe, si no esta activo o si la categoria no tiene precio
+    ///Ejemplo
+    ///'''
+    /// if self.crear_pagos(11444666).is_ok(){
+    ///   println!("Se creo el pago");
+    /// }
+    ///'''
+    #[ink(message)]
+    pub fn crear_pagos(&mut self, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      self.aplicar_cambios_programados();
+      //busco el socio para obtener su categoria
+      let socio=self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado != EstadoSocio::Activo{
+        return Err(ClubError::SocioInactivo);
+      }
+      if let Some(f)=self.ultimo_vencimiento(id_socio){ 
+        self.crear_siguiente_pago(socio, f)?;
+      }
+      Ok(())
+    }  
+    ///Devuelve la fecha de vencimiento del ultimo pago mensual del socio, o "None" si el socio no tiene pagos
+    ///Ejemplo
+    ///'''
+    /// let fecha:Option<u64> = self.ultimo_vencimiento(44851840);
+    ///'''
+    fn ultimo_vencimiento(&self, id_socio: u128)->Option<u64>{
+      let ids = self.pagos_socio.get(id_socio)?;
+      //las cuotas de un plan no cuentan, los pagos mensuales siguen su propio calendario
+      ids.iter().rev().filter_map(|id| self.pagos.get(id)).find(|pago| !pago.cuota_plan).map(|pago| pago.fecha_vencimiento)
+    }
+    ///Crea el pago siguiente de un socio, que vence 30 dias despues de la fecha de vencimiento anterior
+    ///El costo es el precio de su categoria mas la cuota extra de sus actividades
+    ///Si la racha de pagos sin atraso del socio llego a "cantidad_meses" desde el ultimo descuento, se le otorga el descuento
+    ///Si el socio tiene saldo a favor se usa para cubrir el pago, y si lo cubre entero el pago queda pagado
+    ///Guarda el pago, lo agrega a los pendientes o a los realizados del socio y emite los eventos correspondientes
+    ///Devuelve el error CategoriaInvalida si la categoria del socio no tiene precio
+    ///Ejemplo
+    ///'''
+    /// self.crear_siguiente_pago(socio, fecha_anterior)?;
+    ///'''
+    fn crear_siguiente_pago(&mut self, mut socio: Socio, fecha_anterior: u64)->Result<(), ClubError>{
+      let id_socio = socio.id;
+      let mut ok = false;
+      let mut costo = self.calcular_cuota(&socio)?;
+      let mut descuento = 0;
+      if socio.puede_tener_descuento(self.cantidad_meses) {
+        //se otorga el descuento
+        let des=self.descuento_otorgado(costo);
+        if let Some(d)=des{
+          let i=costo.checked_sub(d);
+          if let Some(a)=i{
+             costo=a;
+             descuento=d;
+             ok=true;
+             //la racha sigue, pero el proximo descuento cuenta desde aca
+             socio.racha_descuento = socio.pagos_sin_atrasos;
+          }  
+        }
+      }
+      let id = self.cantidad_pagos + 1;
+      let fecha_pago: Option<u64> = None;
+      let fecha_vencimiento=fecha_anterior+ (30 * 24 * 60 * 60);
+      
+      let mut pago:Pago=Pago::crear_pago(id_socio, id, costo, fecha_vencimiento, fecha_pago, false, ok);
+      //se usa el saldo a favor del socio, que ya habia entrado a la tesoreria
+      let saldo = self.saldos_a_favor.get(id_socio).unwrap_or(0);
+      let usado = saldo.min(costo);
+      pago.monto_pagado = usado;
+      if usado > 0 && usado == costo{
+        pago.pagado = true;
+        pago.fecha_pago = Some(self.env().block_timestamp());
+        socio.pagos_realizados.push(id);
+        socio.registrar_puntualidad(&pago);
+      }else{
+        socio.pagos_pendientes.push(id);
+      }
+      self.agregar_pago(&pago);
+      self.socios.insert(id_socio, &socio);
+      let caller = self.env().caller();
+      self.env().emit_event(PagoCreado{ id_socio, caller, id, costo, fecha_vencimiento });
+      if ok{
+        self.env().emit_event(DescuentoAplicado{ id_socio, caller, id, descuento });
+      }
+      if usado > 0{
+        self.saldos_a_favor.insert(id_socio, &(saldo - usado));
+        self.env().emit_event(SaldoAplicado{ id_socio, id, monto: usado });
+      }
+      Ok(())
+    }
+    ///Crea el pago siguiente de todos los socios activos cuyo ultimo pago ya vencio, aplicando el descuento igual que crear_pagos
+    ///Recorre los socios en orden de registro a partir del cursor guardado, procesando como mucho "limite_facturacion" socios por llamada
+    ///Si quedan socios por recorrer guarda el cursor para seguir en la proxima llamada, y si termina lo vuelve a 0
+    ///Los periodos son de 30 dias contados desde la fecha 0, y a cada socio se le crea como mucho un pago por periodo, aunque deba varios meses,
+    ///por lo que volver a llamarla en el mismo periodo no crea pagos repetidos. Los socios cuyo ultimo pago todavia no vencio tambien se saltean
+    ///Si no se le puede crear el pago a un socio se emite el evento SocioNoFacturado con el error y se sigue con los demas
+    ///Devuelve true si se termino de recorrer a todos los socios, o el error NoAutorizado si no esta permitido
+    ///Ejemplo
+    ///'''
+    /// while !self.facturar_periodo()?{}
+    ///'''
+    #[ink(message)]
+    pub fn facturar_periodo(&mut self)->Result<bool, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      self.aplicar_cambios_programados();
+      let ahora = self.env().block_timestamp();
+      let periodo = ahora / PERIODO_FACTURACION;
+      let desde = self.cursor_facturacion;
+      let hasta = desde.saturating_add(self.limite_facturacion).min(self.cantidad_socios);
+      let (mut creados, mut fallidos) = (0, 0);
+      for pos in desde..hasta{
+        if let Some(socio) = self.orden_socios.get(pos).and_then(|id_socio| self.buscar_socio(id_socio)).filter(|s| s.estado == EstadoSocio::Activo){
+          let id_socio = socio.id;
+          if self.periodo_facturado.get(id_socio) == Some(periodo){
+            continue;
+          }
+          if let Some(f) = self.ultimo_vencimiento(id_socio).filter(|f| *f <= ahora){
+            match self.crear_siguiente_pago(socio, f){
+              Ok(()) => {
+                self.periodo_facturado.insert(id_socio, &periodo);
+                creados += 1;
+              },
+              Err(error) => {
+                self.env().emit_event(SocioNoFacturado{ id_socio, error });
+                fallidos += 1;
+              },
+            }
+          }
+        }
+      }
+      let terminado = hasta >= self.cantidad_socios;
+      self.cursor_facturacion = if terminado { 0 } else { hasta };
+      let caller = self.env().caller();
+      self.env().emit_event(FacturacionProcesada{ caller, desde, hasta, creados, fallidos, terminado });
+      Ok(terminado)
+    }
+    ///Cambia la cantidad maxima de socios que recorre cada llamada a facturar_periodo
+    ///Devuelve el error ContratoEnPausa, NoAutorizado si quien llama no tiene permiso, o LimiteInvalido si el limite es 0
+    ///Ejemplo
+    ///'''
+    ///   self.set_limite_facturacion(100)?;
+    ///   assert_eq!(self.limite_facturacion,100);
+    ///'''
+    #[ink(message)]
+    pub fn set_limite_facturacion(&mut self, limite: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if limite == 0{
+        return Err(ClubError::LimiteInvalido);
+      }
+      self.limite_facturacion = limite;
+      Ok(())
+    }
+    ///Crea una categoria nueva en el catalogo, con el nombre, el precio mensual y las actividades que incluye
+    ///La categoria se crea activa y se le asigna el proximo id, que es el que devuelve
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, o CategoriaExistente si ya hay una categoria con ese nombre
+    ///Ejemplo
+    ///'''
+    /// let id = self.crear_categoria("D".to_string(), 1000, ActividadesIncluidas::Cantidad(2))?;
+    ///'''
+    #[ink(message)]
+    pub fn crear_categoria(&mut self, nombre: String, precio: u128, actividades: ActividadesIncluidas)->Result<u32, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.buscar_categoria(&nombre).is_some(){
+        return Err(ClubError::CategoriaExistente);
+      }
+      let id = self.cantidad_categorias + 1;
+      let categoria = Categoria::crear_categoria(id, nombre.clone(), precio, actividades);
+      self.categorias.insert(id, &categoria);
+      self.cantidad_categorias = id;
+      let caller = self.env().caller();
+      self.env().emit_event(CategoriaCreada{ caller, id, nombre, precio });
+      Ok(id)
+    }
+    ///Edita el nombre, el precio y las actividades de una categoria del catalogo
+    ///Los pagos ya creados mantienen su costo, el precio nuevo se usa a partir de los proximos pagos y queda en el historial de precios
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, CategoriaInvalida si no existe la categoria
+    ///o CategoriaExistente si el nombre nuevo ya lo usa otra categoria
+    ///Con la aprobacion multiple activa el precio no se puede cambiar aca, devuelve RequiereAprobacion y se tiene que proponer con proponer_cambio
+    ///Ejemplo
+    ///'''
+    /// self.editar_categoria(1, "A".to_string(), 7000, ActividadesIncluidas::Todas)?;
+    ///'''
+    #[ink(message)]
+    pub fn editar_categoria(&mut self, id: u32, nombre: String, precio: u128, actividades: ActividadesIncluidas)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut categoria = self.categorias.get(id).ok_or(ClubError::CategoriaInvalida)?;
+      if self.buscar_categoria(&nombre).is_some_and(|c| c.id != id){
+        return Err(ClubError::CategoriaExistente);
+      }
+      if precio != categoria.precio && self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      if precio != categoria.precio{
+        self.registrar_precio(id, categoria.precio, precio, self.env().block_timestamp());
+      }
+      categoria.nombre = nombre.clone();
+      categoria.precio = precio;
+      categoria.actividades = actividades;
+      self.categorias.insert(id, &categoria);
+      let caller = self.env().caller();
+      self.env().emit_event(CategoriaEditada{ caller, id, nombre, precio });
+      Ok(())
+    }
+    ///Retira una categoria del catalogo, para que no se registren socios nuevos en ella
+    ///Los socios que ya tienen esa categoria la mantienen y se les siguen creando los pagos con su precio
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, o CategoriaInvalida si no existe o ya estaba retirada
+    ///Ejemplo
+    ///'''
+    /// self.retirar_categoria(3)?;
+    ///'''
+    #[ink(message)]
+    pub fn retirar_categoria(&mut self, id: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut categoria = self.categorias.get(id).filter(|c| c.activa).ok_or(ClubError::CategoriaInvalida)?;
+      categoria.activa = false;
+      self.categorias.insert(id, &categoria);
+      let caller = self.env().caller();
+      self.env().emit_event(CategoriaRetirada{ caller, id });
+      Ok(())
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve todas las categorias del catalogo ordenadas por id, incluidas las retiradas
+    /// si no devuelve un vector vacio
+    /// Ejemplo
+    /// '''
+    ///   let categorias:Vec<Categoria> = self.get_categorias();
+    /// '''
+    #[ink(message)]
+    pub fn get_categorias(&self)->Vec<Categoria>{
+      let mut vec:Vec<Categoria>=Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        vec = (1..=self.cantidad_categorias).filter_map(|id| self.categorias.get(id)).collect();
+      }
+      vec
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve los nombres de todas las categorias del catalogo ordenados por id
+    /// si no devuelve un vector vacio
+    /// Ejemplo
+    /// '''
+    ///   assert_eq!(self.get_nombres_categorias(), ["A".to_string(), "B".to_string(), "C".to_string()].to_vec());
+    /// '''
+    #[ink(message)]
+    pub fn get_nombres_categorias(&self)->Vec<String>{
+      self.get_categorias().into_iter().map(|c| c.nombre).collect()
+    }
+    ///Programa un cambio de precio de una categoria o del descuento, que rige desde la fecha indicada
+    ///Hasta esa fecha se sigue usando el valor actual, y desde esa fecha los pagos nuevos usan el valor programado
+    ///Devuelve el id del cambio, o el error NoAutorizado si quien llama no tiene permiso, RequiereAprobacion si la aprobacion multiple esta activa,
+    ///CategoriaInvalida si no existe la categoria o FechaInvalida si la fecha no es posterior a la actual
+    ///Ejemplo
+    ///'''
+    /// let id = self.programar_cambio(ValorProgramado::PrecioCategoria(1, 6000), fecha_1_de_marzo)?;
+    ///'''
+    #[ink(message)]
+    pub fn programar_cambio(&mut self, valor: ValorProgramado, fecha_vigencia: u64)->Result<u32, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if self.aprobacion_multiple(){
+        return Err(ClubError::RequiereAprobacion);
+      }
+      self.validar_programado(&valor, fecha_vigencia)?;
+      Ok(self.agendar_cambio(valor, fecha_vigencia))
+    }
+    ///Se fija que la categoria de un cambio de precio exista y que la fecha desde la que rige sea posterior a la actual
+    fn validar_programado(&self, valor: &ValorProgramado, fecha_vigencia: u64)->Result<(), ClubError>{
+      if let ValorProgramado::PrecioCategoria(id, _) = valor{
+        if !self.categorias.contains(id){
+          return Err(ClubError::CategoriaInvalida);
+        }
+      }
+      if fecha_vigencia <= self.env().block_timestamp(){
+        return Err(ClubError::FechaInvalida);
+      }
+      Ok(())
+    }
+    ///Guarda un cambio programado con el proximo id, emite el evento y devuelve el id
+    fn agendar_cambio(&mut self, valor: ValorProgramado, fecha_vigencia: u64)->u32{
+      let id = self.cantidad_cambios_programados + 1;
+      let caller = self.env().caller();
+      self.cambios_programados.push(CambioProgramado{ id, valor, fecha_vigencia, caller });
+      self.cantidad_cambios_programados = id;
+      self.env().emit_event(CambioProgramadoCreado{ id, caller, valor, fecha_vigencia });
+      id
+    }
+    ///Cancela un cambio programado que todavia no se aplico
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, o CambioNoEncontrado si no hay un cambio pendiente con ese id
+    ///Ejemplo
+    ///'''
+    /// self.cancelar_cambio_programado(1)?;
+    ///'''
+    #[ink(message)]
+    pub fn cancelar_cambio_programado(&mut self, id: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      self.aplicar_cambios_programados();
+      let pos = self.cambios_programados.iter().position(|c| c.id == id).ok_or(ClubError::CambioNoEncontrado)?;
+      self.cambios_programados.remove(pos);
+      let caller = self.env().caller();
+      self.env().emit_event(CambioProgramadoCancelado{ id, caller });
+      Ok(())
+    }
+    ///Aplica los cambios programados que ya rigen, en el orden de sus fechas, y los saca de los pendientes
+    ///Los cambios de precio quedan en el historial de precios con la fecha desde la que rigen
+    fn aplicar_cambios_programados(&mut self){
+      let ahora = self.env().block_timestamp();
+      let (mut vigentes, pendientes): (Vec<CambioProgramado>, Vec<CambioProgramado>) =
+        core::mem::take(&mut self.cambios_programados).into_iter().partition(|c| c.fecha_vigencia <= ahora);
+      self.cambios_programados = pendientes;
+      vigentes.sort_by_key(|c| (c.fecha_vigencia, c.id));
+      for cambio in vigentes{
+        match cambio.valor{
+          ValorProgramado::PrecioCategoria(id, precio) => self.cambiar_precio(id, precio, cambio.fecha_vigencia),
+          ValorProgramado::Descuento(descuento) => {
+            self.descuento = descuento;
+            self.emitir_descuento_cambiado();
+          },
+        }
+      }
+    }
+    ///Cambia el precio de una categoria, lo guarda en el historial de precios y emite el evento, si la categoria existe
+    fn cambiar_precio(&mut self, id: u32, precio: u128, fecha: u64){
+      if let Some(mut categoria) = self.categorias.get(id){
+        self.registrar_precio(id, categoria.precio, precio, fecha);
+        categoria.precio = precio;
+        self.categorias.insert(id, &categoria);
+        let caller = self.env().caller();
+        self.env().emit_event(CategoriaEditada{ caller, id, nombre: categoria.nombre, precio });
+      }
+    }
+    ///Agrega un cambio de precio al historial de precios de la categoria
+    fn registrar_precio(&mut self, id: u32, anterior: u128, nuevo: u128, fecha: u64){
+      let mut historial = self.historial_precios.get(id).unwrap_or_default();
+      historial.push(CambioPrecio{ anterior, nuevo, fecha });
+      self.historial_precios.insert(id, &historial);
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve los cambios programados que todavia no se aplicaron, ordenados por id
+    /// si no devuelve un vector vacio
+    /// Ejemplo
+    /// '''
+    ///   let cambios:Vec<CambioProgramado> = self.get_cambios_programados();
+    /// '''
+    #[ink(message)]
+    pub fn get_cambios_programados(&self)->Vec<CambioProgramado>{
+      let mut vec:Vec<CambioProgramado>=Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        vec = self.cambios_programados.clone();
+      }
+      vec
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve los cambios de precio de una categoria, del mas viejo al mas nuevo
+    ///Devuelve el error NoAutorizado si no tiene permiso, o CategoriaInvalida si no existe la categoria
+    ///Ejemplo
+    ///'''
+    /// let historial = self.get_historial_precios(1)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_historial_precios(&self, id: u32)->Result<Vec<CambioPrecio>, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.categorias.contains(id){
+        return Err(ClubError::CategoriaInvalida);
+      }
+      Ok(self.historial_precios.get(id).unwrap_or_default())
+    }
+    ///Devuelve el nombre de la categoria con ese id, o el error CategoriaInvalida si no existe
+    ///Ejemplo
+    ///'''
+    /// let nombre = self.nombre_categoria(1)?;
+    ///'''
+    fn nombre_categoria(&self, id: u32)->Result<String, ClubError>{
+      self.categorias.get(id).map(|c| c.nombre).ok_or(ClubError::CategoriaInvalida)
+    }
+    ///Busca en el registro una actividad por su nombre y la devuelve si el socio la puede elegir
+    ///Devuelve el error ActividadInvalida si no existe o esta retirada, y CupoCompleto si ya no tiene lugar
+    ///Ejemplo
+    ///'''
+    /// let actividad = self.elegir_actividad("HOCKEY")?;
+    ///'''
+    fn elegir_actividad(&self, nombre: &str)->Result<Actividad, ClubError>{
+      let actividad = self.buscar_actividad(nombre).filter(|a| a.activa).ok_or(ClubError::ActividadInvalida)?;
+      if let Some(cupo) = actividad.cupo_maximo{
+        if actividad.inscriptos >= cupo{
+          return Err(ClubError::CupoCompleto);
+        }
+      }
+      Ok(actividad)
+    }
+    ///Resta un inscripto a la actividad con ese id, si existe
+    fn restar_inscripto(&mut self, id: u32){
+      if let Some(mut actividad) = self.actividades.get(id){
+        actividad.inscriptos = actividad.inscriptos.saturating_sub(1);
+        self.actividades.insert(id, &actividad);
+      }
+    }
+    ///Busca en el registro una actividad por su nombre, aunque este retirada, y devuelve una copia o "None" si no existe
+    ///Ejemplo
+    ///'''
+    /// let actividad:Option<Actividad> = self.buscar_actividad("HOCKEY");
+    ///'''
+    fn buscar_actividad(&self, nombre: &str)->Option<Actividad>{
+      (1..=self.cantidad_actividades).filter_map(|id| self.actividades.get(id)).find(|a| a.nombre == nombre)
+    }
+    ///Suma un inscripto a la actividad con ese id, si existe
+    fn sumar_inscripto(&mut self, id: u32){
+      if let Some(mut actividad) = self.actividades.get(id){
+        actividad.inscriptos += 1;
+        self.actividades.insert(id, &actividad);
+      }
+    }
+    ///Inscribe a un socio en una actividad mas, buscandola por nombre en el registro
+    ///La categoria del socio tiene que permitirle mas actividades de las que ya tiene, y la actividad tiene que estar activa y con cupo
+    ///La cuota extra de la actividad se cobra a partir del proximo pago que se le cree al socio
+    ///Devuelve el error NoAutorizado, SocioNoEncontrado, SocioInactivo, LimiteActividades si su categoria no le permite mas actividades,
+    ///YaInscripto si ya la tenia, o el error de la actividad si no se puede elegir
+    ///Ejemplo
+    ///'''
+    /// self.agregar_actividad(44851840, "TENIS".to_string())?;
+    ///'''
+    #[ink(message)]
+    pub fn agregar_actividad(&mut self, id_socio: u128, nombre: String)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado != EstadoSocio::Activo{
+        return Err(ClubError::SocioInactivo);
+      }
+      let categoria = self.categorias.get(socio.categoria).ok_or(ClubError::CategoriaInvalida)?;
+      match categoria.actividades{
+        ActividadesIncluidas::Cantidad(n) if (socio.actividades.len() as u32) < n => (),
+        _ => return Err(ClubError::LimiteActividades),
+      }
+      let actividad = self.elegir_actividad(&nombre)?;
+      if socio.actividades.contains(&actividad.id){
+        return Err(ClubError::YaInscripto);
+      }
+      socio.actividades.push(actividad.id);
+      self.socios.insert(id_socio, &socio);
+      self.sumar_inscripto(actividad.id);
+      let caller = self.env().caller();
+      self.env().emit_event(ActividadAgregada{ id_socio, caller, actividad: actividad.id });
+      Ok(())
+    }
+    ///Da de baja a un socio de una de sus actividades, buscandola por nombre en el registro, aunque este retirada
+    ///Desde el proximo pago que se le cree al socio ya no se le cobra la cuota extra de esa actividad
+    ///Devuelve el error NoAutorizado, SocioNoEncontrado, o NoInscripto si el socio no tiene esa actividad
+    ///Ejemplo
+    ///'''
+    /// self.quitar_actividad(44851840, "TENIS".to_string())?;
+    ///'''
+    #[ink(message)]
+    pub fn quitar_actividad(&mut self, id_socio: u128, nombre: String)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      let actividad = self.buscar_actividad(&nombre).ok_or(ClubError::NoInscripto)?;
+      let pos = socio.actividades.iter().position(|a| *a == actividad.id).ok_or(ClubError::NoInscripto)?;
+      socio.actividades.remove(pos);
+      self.socios.insert(id_socio, &socio);
+      self.restar_inscripto(actividad.id);
+      let caller = self.env().caller();
+      self.env().emit_event(ActividadQuitada{ id_socio, caller, actividad: actividad.id });
+      Ok(())
+    }
+    ///Cambia la categoria de un socio por otra activa del catalogo, buscandola por nombre
+    ///Ajusta el pago pendiente del ultimo periodo del socio prorrateando los dias que le quedan al periodo entre lo que cobra ese pago y la cuota nueva
+    ///Si el pago tenia un descuento, la parte de la cuota nueva lleva el mismo descuento
+    ///No se ajustan las cuotas de un plan ni los pagos refinanciados. Si el pago tenia un pago parcial que ya cubre el costo nuevo,
+    ///el pago queda pagado y lo que se pago de mas se le acredita al socio como saldo a favor
+    ///Si la nueva categoria incluye todas las actividades o ninguna, el socio deja sus actividades, y si no se queda con las primeras que permita
+    ///El cambio se guarda en el historial de categorias del socio
+    ///Devuelve el error NoAutorizado, SocioNoEncontrado, SocioInactivo si se dio de baja, CategoriaInvalida si la categoria no existe o esta retirada,
+    ///o MismaCategoria si el socio ya tiene esa categoria
+    ///Ejemplo
+    ///'''
+    /// self.cambiar_categoria(44851840, "A".to_string())?;
+    ///'''
+    #[ink(message)]
+    pub fn cambiar_categoria(&mut self, id_socio: u128, nueva: String)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado == EstadoSocio::DadoDeBaja{
+        return Err(ClubError::SocioInactivo);
+      }
+      let categoria = self.buscar_categoria(&nueva).filter(|c| c.activa).ok_or(ClubError::CategoriaInvalida)?;
+      if categoria.id == socio.categoria{
+        return Err(ClubError::MismaCategoria);
+      }
+      let cuota_vieja = self.calcular_cuota(&socio)?;
+      let anterior = socio.categoria;
+      socio.categoria = categoria.id;
+      //las actividades que no entran en la nueva categoria se dejan
+      let permitidas = match categoria.actividades{
+        ActividadesIncluidas::Todas => 0,
+        ActividadesIncluidas::Cantidad(n) => n as usize,
+      };
+      while socio.actividades.len() > permitidas{
+        if let Some(a) = socio.actividades.pop(){
+          self.restar_inscripto(a);
+        }
+      }
+      let cuota_nueva = self.calcular_cuota(&socio)?;
+      //se prorratea el pago del ultimo periodo con los dias que le quedan, si todavia no esta pagado
+      let fecha = self.env().block_timestamp();
+      let caller = self.env().caller();
+      let mut costo_anterior = None;
+      let mut costo_nuevo = None;
+      let mut excedente = 0;
+      let ultimo = socio.pagos_pendientes.iter().rev().filter_map(|id| self.pagos.get(id)).find(|pago| !pago.cuota_plan);
+      if let Some(mut pago) = ultimo.filter(|pago| !pago.pagado && !pago.refinanciado){
+        let periodo:u64 = 30 * 24 * 60 * 60;
+        let restante = pago.fecha_vencimiento.saturating_sub(fecha).min(periodo) as u128;
+        //la cuota nueva se ajusta en la misma proporcion que el pago a la cuota vieja, para mantener el descuento que tenia
+        let nueva_ajustada = (cuota_nueva * pago.costo).checked_div(cuota_vieja).unwrap_or(cuota_nueva);
+        let costo = pago.costo - pago.costo * restante / periodo as u128 + nueva_ajustada * restante / periodo as u128;
+        costo_anterior = Some(pago.costo);
+        costo_nuevo = Some(costo);
+        pago.costo = costo;
+        //si lo pagado parcialmente ya cubre el costo nuevo, el pago queda saldado
+        if pago.monto_pagado >= costo{
+          excedente = pago.monto_pagado - costo;
+          pago.monto_pagado = costo;
+          pago.pagado = true;
+          pago.fecha_pago = Some(fecha);
+          socio.pagos_pendientes.retain(|id| *id != pago.id);
+          socio.pagos_realizados.push(pago.id);
+          socio.registrar_puntualidad(&pago);
+          self.env().emit_event(PagoRegistrado{ id_socio, caller, id: pago.id, monto: costo });
+        }
+        self.pagos.insert(pago.id, &pago);
+      }
+      self.socios.insert(id_socio, &socio);
+      let mut historial = self.historial_categorias.get(id_socio).unwrap_or_default();
+      historial.push(CambioCategoria{ anterior, nueva: categoria.id, fecha, costo_anterior, costo_nuevo });
+      self.historial_categorias.insert(id_socio, &historial);
+      self.env().emit_event(CategoriaCambiada{ id_socio, caller, anterior, nueva: categoria.id });
+      self.acreditar(id_socio, excedente, String::from("cambio de categoria"));
+      Ok(())
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve los cambios de categoria de un socio, del mas viejo al mas nuevo
+    ///Devuelve el error NoAutorizado si no tiene permiso, o SocioNoEncontrado si no existe el socio
+    ///Ejemplo
+    ///'''
+    /// let historial = self.get_historial_categorias(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_historial_categorias(&self, id_socio: u128)->Result<Vec<CambioCategoria>, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if !self.existe_socio(id_socio){
+        return Err(ClubError::SocioNoEncontrado);
+      }
+      Ok(self.historial_categorias.get(id_socio).unwrap_or_default())
+    }
+    ///Si quien llama tiene permiso de consulta, devuelve la racha de pagos seguidos sin atraso de un socio
+    ///y cuantos pagos sin atraso le faltan para que se le otorgue el descuento en su proximo pago
+    ///Devuelve el error NoAutorizado si no tiene permiso, o SocioNoEncontrado si no existe el socio
+    ///Ejemplo
+    ///'''
+    /// let (racha, faltan) = self.get_racha_pagos(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_racha_pagos(&self, id_socio: u128)->Result<(u128, u128), ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      let objetivo = socio.racha_descuento.saturating_add(self.cantidad_meses);
+      Ok((socio.pagos_sin_atrasos, objetivo.saturating_sub(socio.pagos_sin_atrasos)))
+    }
+    ///Suspende a un socio activo guardando el motivo, mientras esta suspendido no se le crean pagos nuevos ni puede hacer actividades
+    ///Devuelve el error NoAutorizado, SocioNoEncontrado, o EstadoInvalido si el socio no esta activo
+    ///Ejemplo
+    ///'''
+    /// self.suspender_socio(44851840, "falta de pago".to_string())?;
+    ///'''
+    #[ink(message)]
+    pub fn suspender_socio(&mut self, id_socio: u128, motivo: String)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado != EstadoSocio::Activo{
+        return Err(ClubError::EstadoInvalido);
+      }
+      socio.estado = EstadoSocio::Suspendido;
+      socio.motivo_estado = Some(motivo.clone());
+      self.socios.insert(id_socio, &socio);
+      let caller = self.env().caller();
+      self.env().emit_event(SocioSuspendido{ id_socio, caller, motivo });
+      Ok(())
+    }
+    ///Da de baja a un socio activo o suspendido, que tiene que transferir junto con la llamada lo que le falta pagar de sus pagos pendientes con los recargos por atraso
+    ///Si tiene saldo a favor se usa primero, y solo tiene que transferir lo que el saldo no cubre
+    ///Los pagos pendientes se marcan como pagados y entran a la tesoreria. Quien llama queda como pagador solo de los pagos que cubrio con lo transferido,
+    ///los que se cubrieron enteros con el saldo a favor quedan sin pagador
+    ///Lo que se transfirio de mas y el saldo a favor que sobre son del socio: si tiene una cuenta vinculada se le devuelven a esa cuenta,
+    ///salvo el saldo a favor si la tesoreria no alcanza para devolverlo. Si no tiene cuenta vinculada el excedente se le acredita como saldo a favor,
+    ///y el saldo que no se devuelve le queda acreditado, se puede consultar con get_saldo_a_favor y se usa en sus pagos si se reincorpora
+    ///El socio deja todas sus actividades y su grupo familiar, si es el titular el grupo se disuelve, y no se le crean mas pagos
+    ///Devuelve el error NoAutorizado, SocioNoEncontrado, EstadoInvalido si ya estaba dado de baja, o MontoIncorrecto si no alcanza para saldar lo pendiente
+    ///Ejemplo
+    ///'''
+    /// self.dar_de_baja(44851840)?; //transfiriendo el total pendiente
+    ///'''
+    #[ink(message, payable)]
+    pub fn dar_de_baja(&mut self, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let monto = self.env().transferred_value();
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado == EstadoSocio::DadoDeBaja{
+        return Err(ClubError::EstadoInvalido);
+      }
+      let fecha_hoy = self.env().block_timestamp();
+      let pendientes:Vec<Pago> = socio.pagos_pendientes.iter().filter_map(|id| self.pagos.get(id)).filter(|pago| !pago.pagado).collect();
+      let total:u128 = pendientes.iter().map(|pago| pago.saldo(self.calcular_recargo(pago, fecha_hoy))).sum();
+      let saldo_a_favor = self.saldos_a_favor.get(id_socio).unwrap_or(0);
+      let a_transferir = total.saturating_sub(saldo_a_favor);
+      if monto < a_transferir{
+        return Err(ClubError::MontoIncorrecto);
+      }
+      //se saldan todos los pagos pendientes, con sus recargos, primero con el saldo a favor y despues con lo transferido
+      let caller = self.env().caller();
+      let mut saldo_restante = saldo_a_favor;
+      for mut pago in pendientes{
+        pago.recargo = self.calcular_recargo(&pago, fecha_hoy);
+        let saldo = pago.saldo(pago.recargo);
+        let del_saldo = saldo_restante.min(saldo);
+        saldo_restante -= del_saldo;
+        pago.monto_pagado += saldo;
+        pago.pagado = true;
+        pago.fecha_pago = Some(fecha_hoy);
+        if del_saldo < saldo{
+          pago.pagador = Some(caller);
+          self.registrar_aporte(pago.id, caller, saldo - del_saldo);
+        }
+        self.pagos.insert(pago.id, &pago);
+        socio.pagos_realizados.push(pago.id);
+        socio.registrar_puntualidad(&pago);
+        self.env().emit_event(PagoRegistrado{ id_socio, caller, id: pago.id, monto: saldo });
+      }
+      socio.pagos_pendientes.clear();
+      for a in socio.actividades.drain(..).collect::<Vec<u32>>(){
+        self.restar_inscripto(a);
+      }
+      socio.estado = EstadoSocio::DadoDeBaja;
+      socio.motivo_estado = None;
+      self.socios.insert(id_socio, &socio);
+      self.salir_de_grupo(id_socio);
+      self.tesoreria += a_transferir;
+      //lo que sobra es del socio
+      let excedente = monto - a_transferir;
+      self.saldos_a_favor.insert(id_socio, &saldo_restante);
+      if let Some(cuenta) = self.cuenta_de_socio.get(id_socio){
+        //el saldo a favor sale de la tesoreria, y si no alcanza queda acreditado, el excedente ya esta en el balance pero no en la tesoreria
+        let mut devuelto = 0;
+        if saldo_restante > 0{
+          let libre = self.env().balance().saturating_sub(self.total_reembolsos_pendientes + excedente);
+          if saldo_restante <= self.tesoreria.min(libre){
+            devuelto = saldo_restante;
+          }
+        }
+        if excedente + devuelto > 0{
+          self.saldos_a_favor.insert(id_socio, &(saldo_restante - devuelto));
+          self.tesoreria -= devuelto;
+          self.devolver(cuenta, excedente + devuelto);
+          self.env().emit_event(SaldoDevuelto{ id_socio, cuenta, monto: excedente + devuelto });
+        }
+      }else{
+        self.tesoreria += excedente;
+        self.acreditar(id_socio, excedente, String::from("excedente de la baja"));
+      }
+      self.env().emit_event(SocioDadoDeBaja{ id_socio, caller, monto: total });
+      Ok(())
+    }
+    ///Vuelve a activar a un socio suspendido o dado de baja
+    ///Si el periodo de su ultimo pago ya vencio, se le crea un pago pendiente nuevo desde hoy, como cuando se registra
+    ///Devuelve el error NoAutorizado, SocioNoEncontrado, o EstadoInvalido si el socio ya estaba activo
+    ///Ejemplo
+    ///'''
+    /// self.reincorporar_socio(44851840)?;
+    ///'''
+    #[ink(message)]
+    pub fn reincorporar_socio(&mut self, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado == EstadoSocio::Activo{
+        return Err(ClubError::EstadoInvalido);
+      }
+      let ahora = self.env().block_timestamp();
+      let costo = self.calcular_cuota(&socio)?;
+      socio.estado = EstadoSocio::Activo;
+      socio.motivo_estado = None;
+      if self.ultimo_vencimiento(id_socio).is_none_or(|f| f <= ahora){
+        let id = self.crear_pago_pendiente(id_socio, costo, ahora);
+        socio.pagos_pendientes.push(id);
+      }
+      self.socios.insert(id_socio, &socio);
+      let caller = self.env().caller();
+      self.env().emit_event(SocioReincorporado{ id_socio, caller });
+      Ok(())
+    }
+    ///Crea un grupo familiar con un socio como titular, que es el que se hace cargo de pagar la deuda del grupo, y el descuento en porcentaje para la cuota de sus integrantes
+    ///El grupo se crea sin integrantes a cargo del titular y se le asigna el proximo id, que es el que devuelve
+    ///El descuento se aplica desde el proximo pago que se le cree a cada integrante, incluido el titular
+    ///Devuelve el error NoAutorizado, DescuentoInvalido si es mayor a 100, SocioNoEncontrado, SocioInactivo si el titular se dio de baja o SocioEnGrupo si ya pertenece a un grupo
+    ///Ejemplo
+    ///'''
+    /// let id = self.crear_grupo(44851840, 15)?;
+    ///'''
+    #[ink(message)]
+    pub fn crear_grupo(&mut self, titular: u128, descuento: u128)->Result<u32, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if descuento > 100{
+        return Err(ClubError::DescuentoInvalido);
+      }
+      self.validar_integrante(titular)?;
+      let id = self.cantidad_grupos.get().unwrap_or(0) + 1;
+      let grupo = GrupoFamiliar{ id, titular, integrantes: Vec::new(), descuento, activo: true };
+      self.grupos.insert(id, &grupo);
+      self.grupo_de_socio.insert(titular, &id);
+      self.cantidad_grupos.set(&id);
+      let caller = self.env().caller();
+      self.env().emit_event(GrupoCreado{ id, titular, caller, descuento });
+      Ok(id)
+    }
+    ///Suma un socio a un grupo familiar, a cargo del titular, y desde su proximo pago se le aplica el descuento del grupo
+    ///Devuelve el error NoAutorizado, GrupoNoEncontrado, SocioNoEncontrado, SocioInactivo si se dio de baja o SocioEnGrupo si ya pertenece a un grupo
+    ///Ejemplo
+    ///'''
+    /// self.agregar_integrante(1, 44851841)?;
+    ///'''
+    #[ink(message)]
+    pub fn agregar_integrante(&mut self, id: u32, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let mut grupo = self.grupos.get(id).filter(|g| g.activo).ok_or(ClubError::GrupoNoEncontrado)?;
+      self.validar_integrante(id_socio)?;
+      grupo.integrantes.push(id_socio);
+      self.grupos.insert(id, &grupo);
+      self.grupo_de_socio.insert(id_socio, &id);
+      let caller = self.env().caller();
+      self.env().emit_event(IntegranteAgregado{ id, id_socio, caller });
+      Ok(())
+    }
+    ///Saca a un socio a cargo del titular de un grupo familiar, y desde su proximo pago ya no se le aplica el descuento del grupo
+    ///Devuelve el error NoAutorizado, GrupoNoEncontrado, EsTitular si es el titular del grupo o NoIntegrante si no esta a cargo del titular
+    ///Ejemplo
+    ///'''
+    /// self.quitar_integrante(1, 44851841)?;
+    ///'''
+    #[ink(message)]
+    pub fn quitar_integrante(&mut self, id: u32, id_socio: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
       }
+      let mut grupo = self.grupos.get(id).filter(|g| g.activo).ok_or(ClubError::GrupoNoEncontrado)?;
+      if grupo.titular == id_socio{
+        return Err(ClubError::EsTitular);
+      }
+      let pos = grupo.integrantes.iter().position(|d| *d == id_socio).ok_or(ClubError::NoIntegrante)?;
+      grupo.integrantes.remove(pos);
+      self.grupos.insert(id, &grupo);
+      self.grupo_de_socio.remove(id_socio);
+      let caller = self.env().caller();
+      self.env().emit_event(IntegranteQuitado{ id, id_socio, caller });
+      Ok(())
     }
-    ///Cambia el estado de la politica de activada a desactivada/desactivada a activada
+    ///Cambia el descuento en porcentaje de un grupo familiar, que se aplica desde el proximo pago que se le cree a cada integrante
+    ///Devuelve el error NoAutorizado, DescuentoInvalido si es mayor a 100 o GrupoNoEncontrado
     ///Ejemplo
     ///'''
-    /// use club::cambiar_politica
-    /// self.cambiar_politica();
+    /// self.set_descuento_grupo(1, 20)?;
     ///'''
     #[ink(message)]
-    pub fn cambiar_politica(&mut self ){
-     if self.es_owner(){
-       self.politica = !self.politica;
-     }
+    pub fn set_descuento_grupo(&mut self, id: u32, descuento: u128)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if descuento > 100{
+        return Err(ClubError::DescuentoInvalido);
+      }
+      let mut grupo = self.grupos.get(id).filter(|g| g.activo).ok_or(ClubError::GrupoNoEncontrado)?;
+      grupo.descuento = descuento;
+      self.grupos.insert(id, &grupo);
+      let caller = self.env().caller();
+      self.env().emit_event(DescuentoGrupoCambiado{ id, caller, descuento });
+      Ok(())
     }
-    ///Acepta o rechaza el pedido de una direccion que quiere relizar cambios en el contrato, devuelve un booleano
-    ///Un pedido se acepta si la politica está activada, o si quien quiere realizar un cambio es el owner o alguna direccion que tenga permiso
+    ///Disuelve un grupo familiar, sus socios siguen en el club pero se facturan por separado y sin el descuento del grupo
+    ///Devuelve el error NoAutorizado o GrupoNoEncontrado si no existe o ya estaba disuelto
     ///Ejemplo
     ///'''
-    /// if aceptar_modificaciones(){
-    ///   let suma = 1 + 1;
-    /// }
+    /// self.disolver_grupo(1)?;
     ///'''
-   fn aceptar_modificaciones(&self)->bool{
-     let mut ok = true;
-     if self.politica{
-       ok = self.esta_permitido();
+    #[ink(message)]
+    pub fn disolver_grupo(&mut self, id: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
+      }
+      let grupo = self.grupos.get(id).filter(|g| g.activo).ok_or(ClubError::GrupoNoEncontrado)?;
+      self.disolver(grupo);
+      Ok(())
+    }
+    ///Se fija que un socio se pueda sumar a un grupo familiar: que exista, que no se haya dado de baja y que no pertenezca a otro grupo
+    fn validar_integrante(&self, id_socio: u128)->Result<(), ClubError>{
+      let socio = self.buscar_socio(id_socio).ok_or(ClubError::SocioNoEncontrado)?;
+      if socio.estado == EstadoSocio::DadoDeBaja{
+        return Err(ClubError::SocioInactivo);
+      }
+      if self.grupo_de_socio.contains(id_socio){
+        return Err(ClubError::SocioEnGrupo);
+      }
+      Ok(())
+    }
+    ///Marca un grupo como disuelto, saca a todos sus socios y emite el evento
+    fn disolver(&mut self, mut grupo: GrupoFamiliar){
+      for id_socio in grupo.socios(){
+        self.grupo_de_socio.remove(id_socio);
+      }
+      grupo.integrantes.clear();
+      grupo.activo = false;
+      self.grupos.insert(grupo.id, &grupo);
+      let caller = self.env().caller();
+      self.env().emit_event(GrupoDisuelto{ id: grupo.id, caller });
+    }
+    ///Saca de su grupo familiar a un socio que se da de baja, si es el titular el grupo se disuelve y si no deja de estar a cargo del titular
+    fn salir_de_grupo(&mut self, id_socio: u128){
+      let Some(mut grupo) = self.grupo_de(id_socio) else { return };
+      if grupo.titular == id_socio{
+        self.disolver(grupo);
+      }else{
+        grupo.integrantes.retain(|d| *d != id_socio);
+        self.grupos.insert(grupo.id, &grupo);
+        self.grupo_de_socio.remove(id_socio);
+        let caller = self.env().caller();
+        self.env().emit_event(IntegranteQuitado{ id: grupo.id, id_socio, caller });
       }
-     ok
     }
-    ///Se fija si el que quiere realizar modificaciones esta permitido para hacerlo, devolviendo un booleano
-    ///Los que tienen permiso son el owner y las direcciones que estan guardadas en el vector
+    ///Devuelve una copia del grupo familiar activo al que pertenece un socio, o "None" si no pertenece a ninguno
     ///Ejemplo
     ///'''
-    /// if esta_permitido(){
-    ///   let suma = 1 + 1;
-    /// }
+    /// let grupo:Option<GrupoFamiliar> = self.grupo_de(44851840);
     ///'''
-    fn esta_permitido(&self)->bool{
-      let mut ok= false;
-      let clave=self.env().caller();
-      if self.direcciones.contains(&clave){
-        ok = true;
-      } else {
-        ok = self.es_owner();
-      }
-      return ok;
+    fn grupo_de(&self, id_socio: u128)->Option<GrupoFamiliar>{
+      self.grupo_de_socio.get(id_socio).and_then(|id| self.grupos.get(id)).filter(|g| g.activo)
     }
-    ///Agrega una nueva direccion al vector solo si quien llama es el owner y la direccion no esta en el vector, devuelve un booleano que avisa si se agrego o no
-    /// Si el que llama es el owner y la direccion ya esta en el vector tira un panic indicando que ya esta.
-    /// Si el que llama no es el owner, tira otro panic indicando que no es el owner
+    ///Si quien llama tiene permiso de consulta, devuelve un grupo familiar, aunque este disuelto
+    ///Devuelve el error NoAutorizado si no tiene permiso, o GrupoNoEncontrado si no existe
     ///Ejemplo
     ///'''
-    /// let ok = self.agregar_direccion(account_id);
-    /// if ok{
-    ///   println!("Se agrego la direccion");
-    /// }
+    /// let grupo = self.get_grupo(1)?;
     ///'''
     #[ink(message)]
-    pub fn agregar_direccion(&mut self, clave: AccountId)->bool{
-      let mut ok = false;
-      if self.es_owner(){
-        if !self.direcciones.contains(&clave){
-          self.direcciones.push(clave);
-          ok=true;
-        } else {
-          panic!("La direccion ya esta en el vector");
-        }
-      }else {
-        panic!("No es el owner");
+    pub fn get_grupo(&self, id: u32)->Result<GrupoFamiliar, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
       }
-      return ok;
+      self.grupos.get(id).ok_or(ClubError::GrupoNoEncontrado)
     }
-    ///Elimina una direccion del vector solo si el que llama es el owner y la direccion esta en el vector, retornando un booleano
-    /// Si el que llama es el owner pero la direccion no esta en el vector, tira un panic indicando que esa direccion no esta en el vector
-    /// Si el que llama no es el owner, tira otro panic indicando que no es el owner
+    ///Si quien llama tiene permiso de consulta, devuelve el id de cada grupo familiar activo con sus socios, el titular primero
+    /// si no devuelve un vector vacio
     /// Ejemplo
     /// '''
-    ///  let ok=self.eliminar_direccion(account_id);
-    ///  if (ok){
-    ///   println!("Se elimino la direccion"); 
-    ///   }
+    ///   let grupos:Vec<(u32, Vec<u128>)> = self.get_grupos();
     /// '''
     #[ink(message)]
-    pub fn eliminar_direccion(&mut self, clave: AccountId)->bool{
-      let mut ok = false;
-      if self.es_owner(){
-        let mut i = 0;
-        if self.direcciones.contains(&clave){
-          while self.direcciones[i] != clave{
-            i+=1;
-          }
-          self.direcciones.remove(i);
-          ok = true;
-        }else{panic!("No esta guardada esta clave,por lo q no se puede eliminar");}
-      } else {
-        panic!("No es el owner");
+    pub fn get_grupos(&self)->Vec<(u32, Vec<u128>)>{
+      let mut vec:Vec<(u32, Vec<u128>)>=Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        vec = (1..=self.cantidad_grupos.get().unwrap_or(0)).filter_map(|id| self.grupos.get(id)).filter(|g| g.activo).map(|g| (g.id, g.socios())).collect();
       }
-      ok
+      vec
     }
-    ///Registra un nuevo socio en el club y devuelve un booleano que avisa si se registro o no
-    ///La informacíon del socio se recibe por parametro
-    ///Se necesita saber el dni del socio, la categoria y su actividad
-    ///Para registrarlo necesita saber si se acepta el pedido de modificacion, y se asegura que el socio a registrar no exista ya
-    ///crear el socio y lo pushea en el vector de socios del Club
-    ///Tira un panic en caso que el socio ya exista o si la direccion que intenta registrar a un socio no está perimitida o si la categoria no es la correcta
+    ///Si quien llama tiene permiso de consulta, devuelve lo que debe cada socio de un grupo familiar, el titular primero
+    ///La deuda de cada socio es lo que le falta pagar de sus pagos pendientes mas los recargos al dia de hoy
+    ///Devuelve el error NoAutorizado si no tiene permiso, o GrupoNoEncontrado si no existe o esta disuelto
     ///Ejemplo
     ///'''
-    /// let ok = self.registrar_socio(
-    /// let v = crear_vehiculo();
+    /// let deudas = self.get_deuda_grupo(1)?;
     ///'''
     #[ink(message)]
-    pub fn registrar_socio(&mut self, dni: u128, cate: String, act: String)->bool {
-      let mut ok=false;
-      if self.aceptar_modificaciones(){
-        if !self.existe_socio(dni){
-          let categoria = match &cate as &str{
-            "A"=> TipoCategoria::A,
-            "B"=> TipoCategoria::B,
-            "C" => TipoCategoria::C,
-            _=> panic!("No se encontro la categoria"),
-          };
-      		let costo_mensual = self.calcular_precio(&categoria);
-      		let fecha_registro = self.env().block_timestamp();
-      		let id_pago = self.crear_pago_pendiente(dni, costo_mensual, fecha_registro);
-      		let pagos_realizados:Vec<u128> = Vec::new();
-      		let mut pagos_pendientes:Vec<u128>= Vec::new();
-      		pagos_pendientes.push(id_pago);
-          let pagos_sin_atrasos=0 as u128;
-    			let socio:Socio=Socio::crear_socio(dni, cate, act, fecha_registro, pagos_realizados, pagos_pendientes, pagos_sin_atrasos);
-      		self.socios.push(socio);
-          ok=true;
-        }else{panic!("ya existe el socio");}
-      }else{panic!("no esta permitido para esta clave");}
-      return ok;
-    }
-    ///Se le debe pasar la categoria para que calcule el precio de esa categoria y lo devuelve
-    ///Obtiene el precio mediante el Mapping de precios del strcut Club
-    ///Tira un panic en caso de que no exista dicha categoria
-    ///Ejemplo
-    ///'''
-    /// let precio = self.calcular_precio(&TipoCategoria::A);
-    /// let v = crear_vehiculo();
-    ///'''
-    fn calcular_precio(&self, categoria: &TipoCategoria)->u128{
-    	let precio = match categoria{
-      	TipoCategoria::A => self.precio_categoria.get(categoria),
-        TipoCategoria::B => self.precio_categoria.get(categoria),
-        TipoCategoria::C => self.precio_categoria.get(categoria),
-      };
-      if let Some(p) = precio{
-      	return p
+    pub fn get_deuda_grupo(&self, id: u32)->Result<Vec<(u128, u128)>, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
       }
-      panic!("No hay precio para esa categoria");
+      let grupo = self.grupos.get(id).filter(|g| g.activo).ok_or(ClubError::GrupoNoEncontrado)?;
+      Ok(grupo.socios().into_iter().map(|id_socio| (id_socio, self.deuda_socio(id_socio))).collect())
     }
-    ///Crea el primer pago pendiente de cada socio cuando se registran y lo pushea en el vector de pagos del club
-    ///Recibe el dni del socio, el costo del pago, y la fecha de registro del socio para calcular la fecha de vencimiento
-    ///
-    ///Ejemplo
-    ///'''
-    /// self.crear_pago_pendiente(11444666, 5000, timestamp);
-    /// 
-    ///'''
-    fn crear_pago_pendiente(&mut self, dni_socio: u128, costo: u128, fecha_registro: u64)->u128{
-    	let id = self.pagos.len() as u128 + 1;
-      let fecha_pago: Option<u64>;
-      fecha_pago = None;
-      let pagado=false;
-      let tiene_descuento=false;
-      let fecha_vencimiento = fecha_registro + (10 * 24 * 60 * 60); //le suma 10 dias a la fecha de registro
-      let pago:Pago=Pago::crear_pago(dni_socio, id, costo, fecha_vencimiento, fecha_pago, pagado, tiene_descuento);
-      self.pagos.push(pago);
-      return self.pagos.len() as u128;
+    ///Calcula lo que le falta pagar a un socio de todos sus pagos pendientes mas los recargos al dia de hoy, o 0 si no existe
+    fn deuda_socio(&self, id_socio: u128)->u128{
+      let fecha_hoy = self.env().block_timestamp();
+      let Some(socio) = self.buscar_socio(id_socio) else { return 0 };
+      socio.pagos_pendientes.iter().filter_map(|id| self.pagos.get(id)).filter(|pago| !pago.pagado)
+        .map(|pago| pago.saldo(self.calcular_recargo(&pago, fecha_hoy))).sum()
     }
-    ///Nos busca en el vector del socios del struct club el socio
-    ///Le mandamos por parametro el dni correspondiente y a traves de ese dato, busca si existe un socio con el mismo dni
-    ///Si lo encuntra devuelve la poscion en donde la encontro, en caso contrario devuleve "None" indicando que no se encontro ningun socio con ese dni
+    ///Registra en una sola transferencia el pago de la deuda de un grupo familiar, transfiriendo el monto junto con la llamada
+    ///Lo transferido se aplica a los pagos pendientes de cada socio del grupo, empezando por el titular y siguiendo con sus integrantes a cargo,
+    ///igual que si se pagaran uno por uno con registro_pago. Si no alcanza, el ultimo pago al que llega queda con un pago parcial
+    ///Si se transfirio de mas, el excedente queda como saldo a favor del titular
+    ///Devuelve el error NoAutorizado, GrupoNoEncontrado, SinPagosPendientes si ningun socio del grupo debe nada o MontoIncorrecto si no se transfirio nada
     ///Ejemplo
     ///'''
-    ///   let ok:bool;
-    ///		let i:Option<usize>=self.buscar_socio(44851840);
-    ///		let Some(p)=i{
-    ///			ok=true;
-  	///		}	else{ok=false;} 
-    ///	'''
-    fn buscar_socio(&self,dni_socio:u128)->Option<usize>{
-    	let mut ok=false;
-      let mut i=0;
-      while i < self.socios.len() && !ok{
-      	if self.socios[i].dni == dni_socio{
-        	ok=true
-        } else {
-          i+=1
-        }
-    	}
-      if ok{
-      	return Some(i)
-      }else{
-        None
-      }
-    }
-    ///Nos devulve la informacion del primer pago pendiente que tenga un determinado socio
-    ///Le pasamos la poscion del socio que queremos
-    ///Si el socio tiene pagos pendientes, nos devuelve una copia de la informacion del primer pago pendiente dentro del vector de "pagos_pendientes" del struct de socio
-    ///si no tiene pagos pendientes, devuleve un "None" indicando que no tiene pagos pendientes el socio indicado
-    /// Ejemplo
-    /// '''
-    ///   let ok:bool;
-    ///   let pagos:Option<u128>=self.primero_pagos_pendientes(3 us usize);
-    ///   if let Some(p)=pagos{
-    ///     ok=true;
-    ///   }else{
-    ///     ok=false;
-    ///   }
+    /// self.pagar_grupo(1)?; //transfiriendo 9000
     ///'''
-    fn primero_pagos_pendientes(&self, i: usize)->Option<u128>{
-      self.socios[i].pagos_pendientes.first().copied()
+    #[ink(message, payable)]
+    pub fn pagar_grupo(&mut self, id: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let monto = self.env().transferred_value();
+      if !self.tiene_permiso(Permiso::RegistrarPagos){
+        return Err(ClubError::NoAutorizado);
+      }
+      let grupo = self.grupos.get(id).filter(|g| g.activo).ok_or(ClubError::GrupoNoEncontrado)?;
+      self.aplicar_pago_grupo(grupo, monto)
     }
-    /// Esta funcion nos registra un pago de un socio determinado, le tenemos que pasar el dni y el monto del pago.
-    /// Si el socio existe, se fija si tiene pagos pendientes. Si tiene pagos pendientes, se fija si no fue pagada y si el socio esta pagando lo corresponde.
-    /// Si cumple todo lo anterior, lo saco de la informacion de pagos pendientes del socio y cambio la informacion asociada al vector de pagos del correspodniente socio,por ultimo lo agrego al vector de los pagos realizados del socio.
-    /// Si no cumple alguna de las anteriores condiciones, nos tira su correspondiente panic
-    /// Por Ejemplo si no tiene pagos pendientes el socio, nos tira un panic "No tiene pagos pendientes el socio"
-    /// Ejemplo
+    ///El titular de un grupo familiar paga la deuda de todo su grupo desde su cuenta vinculada, transfiriendo el monto junto con la llamada
+    ///Funciona igual que pagar_grupo, pero no necesita un rol y solo puede pagar el grupo del que es titular
+    ///Devuelve el error CuentaNoVinculada si quien llama no tiene un socio vinculado, NoEsTitular si su socio no es titular de un grupo, o los mismos errores que pagar_grupo
+    ///Ejemplo
     ///'''
-    /// self.registro_pago(44851840, 5000);
-    /// 
+    /// self.pagar_mi_grupo()?; //transfiriendo 9000
     ///'''
-    #[ink(message)]
-    pub fn registro_pago(&mut self,dni_socio:u128,monto:u128){
-      if self.aceptar_modificaciones(){
-        //busco el socio 
-        let i=self.buscar_socio(dni_socio);
-        if let Some(i)=i{
-          //me fijo si tiene pagos pendientes
-          if self.socios[i].pagos_pendientes.len() > 0{
-            //si tiene pagos pendientes, accedo a la primera posicion de pagos pendientes obteniendo la posicion en la q esta guardados los pagos en el vector de pagos
-            let id_pendiente=self.primero_pagos_pendientes(i);
-            if let Some(id)=id_pendiente{ 
-              //si no fue pagada
-              let pos:usize=(id - 1).try_into().unwrap();
-              if self.pagos[pos].pagado == false  {
-                //si el socio paga lo q le corresponde
-                if self.pagos[pos].costo == monto{
-                  //lo saco de la informacion de pagos pendientes del socio
-                  self.socios[i].pagos_pendientes.remove(0);
-                  //cambio el pago asociado al vector de pagos
-                  self.pagos[pos].pagado=true; 
-                  let fecha_pago = Some(self.env().block_timestamp()); //la fecha de hoy
-                  self.pagos[pos].fecha_pago=fecha_pago;
-                  //lo agrego a pagos realizados del socio
-                  self.socios[i].pagos_realizados.push(id);
-                  
-                }else{
-                  panic!("No paga lo q corresponde para su categoria");
-                }
-              }
-            } 
-          }else{
-            panic!("No tiene pagos pendientes el socio");
-          }    
-        } else{
-          panic!("El socio no existe");
-
-        } 
-      }else{
-        panic!("No esta permitido");
-      } 
+    #[ink(message, payable)]
+    pub fn pagar_mi_grupo(&mut self)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      let monto = self.env().transferred_value();
+      let id_socio = self.socio_de_cuenta.get(self.env().caller()).ok_or(ClubError::CuentaNoVinculada)?;
+      let grupo = self.grupo_de(id_socio).filter(|g| g.titular == id_socio).ok_or(ClubError::NoEsTitular)?;
+      self.aplicar_pago_grupo(grupo, monto)
     }
-    ///Esta funcion nos devuelve la informacion de un socio determinado, nos indica su dni, categoria y los pagos realizados
-    ///Le debemos pasar el dni, si le pasamos un dni="None" nos devulve un dni y categoria con valor "None" y un listado de los costos de los ultimos 30 pagos realizados.
-    ///En caso contrario, si le pasamos un dni valido, lo busca y si encuntra el socio devuleve el dni y categoria correspondiente, con el listado de los costos de los pagos realizados.
-    ///Si no encuntra el socio, tira un panic "No se encontro el socio"
-    /// Ejemplo
-    ///'''
-    /// let a:Adicional=self.consultar_pago(Some(44851840));
-    /// assert_eq!(a.dni,44851840);
-    ///'''
-    
-    #[ink(message)]
-    pub fn consultar_pagos(&self,dni_socio:Option<u128>)->Adicional{
-      if self.aceptar_modificaciones(){
-        let p ;
-        let mut dni=None;
-        let mut cate=None;
-        if let Some(id)=dni_socio{
-          dni = Some(id);
-          let i=self.buscar_socio(id);
-          if let Some(pos)=i{
-            cate=Some(self.socios[pos].categoria);
-            p=self.pagos.iter().filter(|pago| pago.dni_socio == id).map(|pago| pago.costo).collect();
-
-          }else{panic!("No se encontro el socio")}
-        } else {
-         p= self.pagos.iter().rev().take(30).map(|pago| pago.costo).collect();
+    ///Reparte el monto transferido entre los pagos pendientes de los socios de un grupo, sin fijarse quien llama
+    ///Lo usan pagar_grupo y pagar_mi_grupo, y devuelve los mismos errores que pagar_grupo salvo NoAutorizado y GrupoNoEncontrado
+    fn aplicar_pago_grupo(&mut self, grupo: GrupoFamiliar, monto: Balance)->Result<(), ClubError>{
+      let socios = grupo.socios();
+      if socios.iter().all(|id_socio| self.deuda_socio(*id_socio) == 0){
+        return Err(ClubError::SinPagosPendientes);
+      }
+      if monto == 0{
+        return Err(ClubError::MontoIncorrecto);
+      }
+      let mut restante = monto;
+      for id_socio in socios{
+        //se pagan los pagos pendientes del socio en orden, hasta que no deba nada o no quede monto
+        while restante > 0{
+          let Ok((costo, recargo)) = self.monto_a_pagar(id_socio) else { break };
+          let aplicado = restante.min(costo + recargo);
+          self.aplicar_pago(id_socio, aplicado)?;
+          restante -= aplicado;
         }
-       Adicional{
-         dni,
-         cate,
-         p,
-       }
-      }else{panic!("No esta permitido")}
+      }
+      //lo que sobra entra a la tesoreria y queda a favor del titular
+      self.tesoreria += restante;
+      self.acreditar(grupo.titular, restante, String::from("excedente de pago del grupo"));
+      let caller = self.env().caller();
+      self.env().emit_event(PagoGrupoRegistrado{ id: grupo.id, caller, monto });
+      Ok(())
     }
-    ///Recibe un monto, calcula el % de descuento de dicho monto y lo retorna, si no lo pudo hacer, devuelve un None
+    ///Calcula la cuota mensual de un socio, que es el precio de su categoria mas la cuota extra de cada una de sus actividades
+    ///Si el socio pertenece a un grupo familiar, a la cuota se le resta el descuento del grupo
+    ///Devuelve el error CategoriaInvalida si la categoria del socio no existe
     ///Ejemplo
     ///'''
-    /// let porciento_del_descuento = self.descuento_otorgado(5000);
-    ///
+    /// let cuota = self.calcular_cuota(&socio)?;
     ///'''
-    fn descuento_otorgado (&self,monto:u128)->Option<u128>{
-      let i=monto.checked_mul(self.descuento);
-      if let Some(a)=i{
-        let s=a.checked_div(100);//le aplico un 30% de descuento 
-        if let Some(d)=s{
-          return Some(d as u128);
+    fn calcular_cuota(&self, socio: &Socio)->Result<u128, ClubError>{
+      let mut cuota = self.calcular_precio(socio.categoria)?;
+      for id in &socio.actividades{
+        if let Some(extra) = self.actividades.get(id).and_then(|a| a.cuota_extra){
+          cuota += extra;
         }
       }
-      return None;
-    }
-    
-    ///Nos cambia el descuento que se le otorga a los socios cuando pagan una cantidad determinada de veces consecutivas sin atrasos
-    ///Le pasamos el descuento nuevo que otorgamos
-    /// Ejemplo
-    /// '''
-    ///   self.set_descuento(5000);
-    ///   assert_eq!(self.descuento,5000);
-    /// '''
-    #[ink(message)]
-    pub fn set_descuento(&mut self, num: u128){
-      if self.aceptar_modificaciones(){
-        self.descuento = num;
+      if let Some(grupo) = self.grupo_de(socio.id){
+        cuota -= cuota * grupo.descuento / 100;
       }
+      Ok(cuota)
     }
-    
-    ///Nos cambia la cantidad de meses que el socio deberia pagar sin atrasos para obtener el descuento.
-    ///Debemos pasarle el numero que queremos cambiar
+    ///Registra una actividad nueva, con su nombre, una cuota extra y un cupo maximo de socios opcionales
+    ///La actividad se crea activa y se le asigna el proximo id, que es el que devuelve
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, ActividadExistente si ya hay una actividad con ese nombre
+    ///o ActividadInvalida si el nombre es "TODOS" o "NADA", que se usan para indicar todas o ninguna actividad
     ///Ejemplo
-    /// '''
-    ///   self.set_cantidad_meses(5);
-    ///   assert_eq!(self.cantidad_meses,5);
-    /// '''
+    ///'''
+    /// let id = self.crear_actividad("VOLEY".to_string(), Some(500), Some(30))?;
+    ///'''
     #[ink(message)]
-    pub fn set_cantidad_meses(&mut self, num: u128){
-      if self.aceptar_modificaciones(){
-        self.cantidad_meses = num;
+    pub fn crear_actividad(&mut self, nombre: String, cuota_extra: Option<u128>, cupo_maximo: Option<u32>)->Result<u32, ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
       }
+      self.validar_nombre_actividad(&nombre, 0)?;
+      let id = self.cantidad_actividades + 1;
+      let actividad = Actividad::crear_actividad(id, nombre.clone(), cuota_extra, cupo_maximo);
+      self.actividades.insert(id, &actividad);
+      self.cantidad_actividades = id;
+      let caller = self.env().caller();
+      self.env().emit_event(ActividadCreada{ caller, id, nombre });
+      Ok(id)
     }
-    ///Crea un nuevo pago y lo pushea en el vector de pagos del club, solo si se acepta el pedido y existe el socio, retorna si se creo o no
-    ///Recibe como parametro el dni del socio
-    ///Busca al socio para obtener los datos restantes
-    ///Calcula la fecha de vencimiento del pago mediante la fecha de vencimiento del pago anterior
-    ///Tambien se fija si el pago va a tener descuento o no, para saber eso se fija si la cantidad de meses sin pagos atrasados consecutivos es la que pide el club
-    ///Tira panic si el socio o la categoria no existe
+    ///Edita el nombre, la cuota extra y el cupo maximo de una actividad registrada
+    ///Si el cupo nuevo es menor a los inscriptos, los socios que ya estan se mantienen pero no se aceptan nuevos
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, ActividadInvalida si no existe la actividad o el nombre no es valido
+    ///o ActividadExistente si el nombre nuevo ya lo usa otra actividad
     ///Ejemplo
     ///'''
-    /// let ok = self.crear_pago(11444666);
-    /// if ok{
-    ///   println!("Se creo el pago");
-    /// }
+    /// self.editar_actividad(1, "FUTBOL".to_string(), Some(300), None)?;
     ///'''
     #[ink(message)]
-    pub fn crear_pagos(&mut self, dni_socio: u128)->bool{
-      if self.aceptar_modificaciones(){
-        if self.existe_socio(dni_socio){
-          let ultimos_pagos:Vec<&Pago>= self.pagos.iter().rev().filter(|pago| pago.dni_socio == dni_socio).map(|pago| pago).take(self.cantidad_meses as usize).collect();
-          let b = ultimos_pagos.iter().filter(|pago| !pago.esta_vencido() && !pago.tiene_descuento).count();
-          let mut ok = false;
-          //busco la categoria del socio,obtengo la posicion 
-          let pos=self.buscar_socio(dni_socio); //devuelve un Option 
-          if let Some(p) = pos{
-            let fecha = self.pagos.iter().rev().filter(|pago|  pago.dni_socio == dni_socio).map(|pago| pago.fecha_vencimiento).next();
-            if let Some(f)=fecha{ 
-              let monto= self.precio_categoria.get(&self.socios[p].categoria);
-              if let Some(mut costo)=monto{ 
-                if b as u128 == self.cantidad_meses {
-                  //se otorga el descuento
-                  let des=self.descuento_otorgado(costo);
-                  if let Some(d)=des{
-                    let i=costo.checked_sub(d);
-                    if let Some(a)=i{
-                       costo=a;
-                       ok=true;
-                    }  
-                  }
-                }
-                let id = self.pagos.len() as u128 + 1;
-                let fecha_pago: Option<u64>;
-                fecha_pago = None;
-                let fecha_vencimiento=f+ (30 * 24 * 60 * 60);
-                
-                let pago:Pago=Pago::crear_pago(dni_socio, id, costo, fecha_vencimiento, fecha_pago, false, ok);
-               
-                self.pagos.push(pago);
-                self.socios[p].pagos_pendientes.push(self.pagos.len() as u128);
-                
-                
-              } else{
-                panic!("No hay precio para esa categoria");
-              } 
-            }
-          }
-        }else{
-          panic!("No se encontro socio");
-        }
-        return true;
-        
-    	}return false;
-    }  
-    ///Nos cambia el monto de la categoria "a", debemos pasarle el monto por el cual lo cambiamos
-    /// Ejemplo
-    /// '''
-    ///   self.set_categoria_a(10000);
-    ///   assert_eq!(self.get_categoria_a,10000);
-    /// '''
-    #[ink(message)]
-    pub fn set_categoria_a(&mut self, monto:u128){
-      if self.aceptar_modificaciones(){
-        self.precio_categoria.insert(TipoCategoria::A, &monto);
+    pub fn editar_actividad(&mut self, id: u32, nombre: String, cuota_extra: Option<u128>, cupo_maximo: Option<u32>)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
       }
+      let mut actividad = self.actividades.get(id).ok_or(ClubError::ActividadInvalida)?;
+      self.validar_nombre_actividad(&nombre, id)?;
+      actividad.nombre = nombre.clone();
+      actividad.cuota_extra = cuota_extra;
+      actividad.cupo_maximo = cupo_maximo;
+      self.actividades.insert(id, &actividad);
+      let caller = self.env().caller();
+      self.env().emit_event(ActividadEditada{ caller, id, nombre });
+      Ok(())
     }
-    ///Nos cambia el monto de la categoria "b", debemos pasarle el monto por el cual se va a cambiar.
-    /// Ejemplo
-    /// '''
-    ///   self.set_categoria_b(5000);
-    ///   assert_eq!(self.get_categoria_b,5000);
-    /// '''
+    ///Retira una actividad del registro, para que los socios nuevos no la puedan elegir
+    ///Los socios que ya la tenian la mantienen
+    ///Devuelve el error NoAutorizado si quien llama no tiene permiso, o ActividadInvalida si no existe o ya estaba retirada
+    ///Ejemplo
+    ///'''
+    /// self.retirar_actividad(7)?;
+    ///'''
     #[ink(message)]
-    pub fn set_categoria_b(&mut self, monto:u128){
-      if self.aceptar_modificaciones(){
-      	self.precio_categoria.insert(TipoCategoria::B, &monto);
+    pub fn retirar_actividad(&mut self, id: u32)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.tiene_permiso(Permiso::Administrar){
+        return Err(ClubError::NoAutorizado);
       }
+      let mut actividad = self.actividades.get(id).filter(|a| a.activa).ok_or(ClubError::ActividadInvalida)?;
+      actividad.activa = false;
+      self.actividades.insert(id, &actividad);
+      let caller = self.env().caller();
+      self.env().emit_event(ActividadRetirada{ caller, id });
+      Ok(())
     }
-    ///Nos cambia el monto de la categoria "c", debemos pasarle el monto por el cual se cambia.
-    /// Ejemplo
-    /// '''
-    ///   self.set_categoria_c(3000);
-    ///   assert_eq!(self.get_categoria_c,3000);
-    /// '''
-    #[ink(message)]
-    pub fn set_categoria_c(&mut self, monto:u128){
-      if self.aceptar_modificaciones(){
-      	self.precio_categoria.insert(TipoCategoria::C, &monto);
+    ///Se fija que el nombre de una actividad no sea "TODOS" ni "NADA" y que no lo use otra actividad distinta a la del id recibido
+    ///Para una actividad nueva se le pasa el id 0
+    fn validar_nombre_actividad(&self, nombre: &str, id: u32)->Result<(), ClubError>{
+      if nombre == "TODOS" || nombre == "NADA"{
+        return Err(ClubError::ActividadInvalida);
       }
-    }
-    ///Si nos acepta el pedido la funcion "aceptar_modificaiones", devulve el monto de la categoria "a" 
-    /// si no devuelve un "None" 
-    /// Ejemplo
-    /// '''
-    ///   assert_eq!(self.get_categoria_a,5000);
-    /// '''
-    
-    #[ink(message)]
-    pub fn get_categoria_a(&mut self)->Option<u128>{
-      let mut precio=None;
-      if self.aceptar_modificaciones(){
-      	precio=self.precio_categoria.get(TipoCategoria::A);
+      let existe = (1..=self.cantidad_actividades).filter_map(|i| self.actividades.get(i)).any(|a| a.nombre == nombre && a.id != id);
+      if existe{
+        return Err(ClubError::ActividadExistente);
       }
-      return precio
+      Ok(())
     }
-    ///Si nos acepta el pedido la funcion "aceptar_modificaiones", devulve el monto de la categoria "b" 
-    /// si no devuelve un "None" 
-     /// Ejemplo
+    ///Si quien llama tiene permiso de consulta, devuelve todas las actividades registradas ordenadas por id, incluidas las retiradas
+    /// si no devuelve un vector vacio
+    /// Ejemplo
     /// '''
-    ///   assert_eq!(self.get_categoria_b,3000);
+    ///   let actividades:Vec<Actividad> = self.get_actividades();
     /// '''
     #[ink(message)]
-    pub fn get_categoria_b(&mut self)->Option<u128>{
-      let mut precio=None;
-      if self.aceptar_modificaciones(){
-      	precio=self.precio_categoria.get(TipoCategoria::B);
+    pub fn get_actividades(&self)->Vec<Actividad>{
+      let mut vec:Vec<Actividad>=Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        vec = (1..=self.cantidad_actividades).filter_map(|id| self.actividades.get(id)).collect();
       }
-      return precio
+      vec
     }
-    ///Si nos acepta el pedido la funcion "aceptar_modificaiones", devulve el monto de la categoria "c" 
-    /// si no devuelve un "None" 
+    ///Si quien llama tiene permiso de consulta, devuelve los nombres de todas las actividades registradas ordenados por id
+    /// si no devuelve un vector vacio
     /// Ejemplo
     /// '''
-    ///   assert_eq!(self.get_categoria_c,2000);
+    ///   let nombres:Vec<String> = self.get_nombres_actividades();
     /// '''
     #[ink(message)]
-    pub fn get_categoria_c(&mut self)->Option<u128>{
-      let mut precio=None;
-      if self.aceptar_modificaciones(){
-      	precio=self.precio_categoria.get(TipoCategoria::C);
-      }
-      return precio
+    pub fn get_nombres_actividades(&self)->Vec<String>{
+      self.get_actividades().into_iter().map(|a| a.nombre).collect()
     }
-    ///Busca y retorna un booleano si existe o no el dni del socio recibido como parametro
+    ///Busca y retorna un booleano si existe o no el id del socio recibido como parametro
     ///Ejemplo
     ///'''
     /// if existe_socio(11444666){
@@ -668,195 +3744,286 @@ mod club{
     /// }
     /// let v = crear_vehiculo();
     ///'''
-    fn existe_socio(&self, dni: u128)->bool{
-      let mut ok = false;
-      let mut i = 0;
-      while i<self.socios.len() && !ok{
-        if self.socios[i].dni == dni {
-          ok = true;
-        }
-        i+=1;
-      }
-      return ok
+    fn existe_socio(&self, id_socio: u128)->bool{
+      self.socios.contains(id_socio)
     }
     
-    ///devulve un listado  de los dni de los socios, si no retorna un vector vacio 
+    ///devulve un listado  de los ids de los socios con su estado, en el orden en que se registraron, si no retorna un vector vacio 
     /// Ejemplo
     /// '''
     ///   let ok:bool;
-    ///   let vec:Vec<u128>=self.get_socio();
+    ///   let vec:Vec<(u128, EstadoSocio)>=self.get_socio();
     ///   if vec.len()==0{
     ///     ok=false;   
     ///   }else{ok=true;}
     /// '''
     #[ink(message)]
-    pub fn get_socios(&self)->Vec<u128>{
-      let mut vec:Vec<u128>=Vec::new();
-      if self.aceptar_modificaciones(){
-        for socio in &self.socios{
-          vec.push(socio.dni);
+    pub fn get_socios(&self)->Vec<(u128, EstadoSocio)>{
+      let mut vec:Vec<(u128, EstadoSocio)>=Vec::new();
+      if self.tiene_permiso(Permiso::Consultar){
+        for i in 0..self.cantidad_socios{
+          if let Some(socio) = self.orden_socios.get(i).and_then(|id_socio| self.buscar_socio(id_socio)){
+            vec.push((socio.id, socio.estado));
+          }
         }
       }
-      return vec;
+      vec
     }
     
-    ///Si nos acepta el pedido la funcion 'aceptar_modificaciones' y encuentra un socio devulve un listado con toda la informacion del dni, fecha de vencimiento, un booleano que indica si fue pagado por el socio y el costo de los pagos del socio que se le pasa por parametro
-    /// si no lo acepta, devuelve un vector vacio 
-    /// si no encunetra el socio, tira un panic informando que no existe
+    ///Si quien llama tiene permiso de consulta y encuentra un socio devulve un listado con toda la informacion del id del socio, fecha de vencimiento, un booleano que indica si ya no se debe porque fue pagado por el socio o se paso a un plan de cuotas, el costo y el recargo por atraso cobrado de los pagos del socio que se le pasa por parametro
+    /// si no tiene permiso, devuelve el error NoAutorizado
+    /// si no encunetra el socio, devuelve el error SocioNoEncontrado
     /// Ejemplo
     /// '''
-    ///   let ok:bool;
-    ///   let vec:Vec<u128>=self.get_pago(44851840);
-    ///   if vec.len()==0{
-    ///     ok=false;   
-    ///   }else{ok=true;}
+    ///   let vec=self.get_pago(44851840)?;
     /// '''
     #[ink(message)]
-    pub fn get_pago(&self, dni: u128)->Vec<(u128, u64, bool,u128)>{
-      let mut vec: Vec<(u128,u64,bool,u128)>=Vec::new();
-      if self.aceptar_modificaciones(){
-        if self.existe_socio(dni){
-          for precio in &self.pagos{
-            if precio.dni_socio == dni{
-              let tupla = (precio.id, precio.fecha_vencimiento, precio.pagado, precio.costo);
-              vec.push(tupla);
-              
-            }
-          }
-        }else{
-          panic!("No existe el socio");
-        }
+    pub fn get_pago(&self, id_socio: u128)->Result<Vec<ResumenPago>, ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
       }
-      return vec;
+      if !self.existe_socio(id_socio){
+        return Err(ClubError::SocioNoEncontrado);
+      }
+      Ok(self.resumen_pagos(id_socio))
+    }
+    ///Arma el resumen de todos los pagos de un socio, sin fijarse quien llama
+    fn resumen_pagos(&self, id_socio: u128)->Vec<ResumenPago>{
+      let mut vec: Vec<ResumenPago>=Vec::new();
+      for precio in self.pagos_de_socio(id_socio){
+        let tupla = (precio.id, precio.fecha_vencimiento, precio.pagado || precio.refinanciado, precio.costo, precio.recargo);
+        vec.push(tupla);
+      }
+      vec
     }
 
-    ///Recibe la posicion de un socio, devolviendo la categoria y la actividad del socio en caso de tener alguna
+    ///Recibe la posicion de un socio, devolviendo el nombre de la categoria y el listado de las actividades del socio
+    ///Si la categoria incluye todas las actividades el listado es solo "TODOS", y si no tiene actividades el listado esta vacio
     ///Retorna la informacion en formato String en caso de que se acepten las modificaciones
-    /// Si la funcion aceptar_modificaciones devuelve false, tira un panic informando que no se acepto el pedido
-    /// Si no se encontro la categoria, tira un panic 
-    ///Ejemplo
-    ///'''
-    /// let info_socio = self.get_info_socio(0);
-    ///'''
-    #[ink(message)]
-    pub fn get_info_socio(&self,i:u128)->(String, String){
-      if self.aceptar_modificaciones(){
-        let cate:String;
-        if i <  self.socios.len() as u128{
-          cate = match self.socios[i as usize].categoria{
-          	TipoCategoria::A=> "A".to_string(),
-          	TipoCategoria::B=> "B".to_string(),
-          	TipoCategoria::C=> "C".to_string(),
-          	_=>panic!("No existe esta categoria"),
-        	};
-        	let mut act = "NADA".to_string();
-          if let Some(a) = &self.socios[i as usize].actividad{
-          	act = match a{
-              Actividad::FUTBOL=>"FUTBOL".to_string(), 
-
-            	Actividad::BASQUET=>"BASQUET".to_string(), 
-
-            	Actividad::RUGBY=>"RUGBY".to_string(), 
-
-            	Actividad::HOCKEY=>"HOCKEY".to_string(), 
-
-            	Actividad::NATACION=>"NATACION".to_string(), 
-
-            	Actividad::TENIS=>"TENIS".to_string(),
-
-            	Actividad::PADDLE=>"PADDLE".to_string(),
-              
-              Actividad::TODOS=>"TODOS".to_string(),
-
-            	_=>panic!("Actividad invalida"),
-            };
-          
-          }
-          return (cate, act);
-        }else{panic!("No es la posicion de socio");}
+    /// Si quien llama no tiene permiso de consulta, devuelve el error NoAutorizado
+    /// Si no hay un socio en esa posicion, devuelve el error PosicionInvalida
+    ///Ejemplo
+    ///'''
+    /// let info_socio = self.get_info_socio(0)?;
+    ///'''
+    #[ink(message)]
+    pub fn get_info_socio(&self,i:u128)->Result<(String, Vec<String>), ClubError>{
+      if !self.tiene_permiso(Permiso::Consultar){
+        return Err(ClubError::NoAutorizado);
+      }
+      if i >= self.cantidad_socios as u128{
+        return Err(ClubError::PosicionInvalida);
+      }
+      let id_socio = self.orden_socios.get(i as u32).ok_or(ClubError::PosicionInvalida)?;
+      let socio = self.buscar_socio(id_socio).ok_or(ClubError::PosicionInvalida)?;
+      let categoria = self.categorias.get(socio.categoria).ok_or(ClubError::CategoriaInvalida)?;
+      let act = if categoria.actividades == ActividadesIncluidas::Todas{
+        ["TODOS".to_string()].to_vec()
+      } else {
+        socio.actividades.iter().filter_map(|id| self.actividades.get(id)).map(|a| a.nombre).collect()
+      };
+      Ok((categoria.nombre, act))
+    }
+    ///Importa socios y pagos que se guardaban en los vectores de la version anterior del contrato
+    ///Los registros se exportan de la version anterior y se cargan por tandas, para no pasarse del limite de gas
+    ///Los registros tienen que venir con el id de cada socio ya calculado fuera de la cadena con calcular_id_socio, para que los dni no queden en la transaccion
+    ///Los pagos mantienen su id, y se agregan a los pagos de cada socio en orden de id
+    ///Solo el owner puede migrar los datos
+    ///Devuelve un error si no es el owner, si un socio ya existe, si un pago ya existe o si un pago es de un socio que no existe
+    ///Ejemplo
+    ///'''
+    /// self.migrar_datos(socios, pagos)?;
+    ///'''
+    #[ink(message)]
+    pub fn migrar_datos(&mut self, socios: Vec<Socio>, pagos: Vec<Pago>)->Result<(), ClubError>{
+      self.verificar_pausa()?;
+      if !self.es_owner(){
+        return Err(ClubError::NoAutorizado);
       }
-      panic!("No se acepta el pedido de lectura");
+      let mut pagos = pagos;
+      //se valida toda la tanda antes de guardar algo
+      for (i, socio) in socios.iter().enumerate(){
+        if self.existe_socio(socio.id) || socios[..i].iter().any(|s| s.id == socio.id){
+          return Err(ClubError::SocioExistente);
+        }
+      }
+      pagos.sort_by_key(|pago| pago.id);
+      for (i, pago) in pagos.iter().enumerate(){
+        if pago.id == 0 || self.pagos.contains(pago.id) || (i > 0 && pagos[i-1].id == pago.id){
+          return Err(ClubError::PagoExistente);
+        }
+        if !self.existe_socio(pago.id_socio) && !socios.iter().any(|s| s.id == pago.id_socio){
+          return Err(ClubError::SocioNoEncontrado);
+        }
+      }
+      for socio in &socios{
+        self.agregar_socio(socio);
+        for a in &socio.actividades{
+          self.sumar_inscripto(*a);
+        }
+      }
+      for pago in &pagos{
+        self.agregar_pago(pago);
+      }
+      Ok(())
     }
   }
     
   impl Socio{
+    ///Actualiza la racha de pagos sin atraso del socio con un pago que acaba de pagar
+    ///Si lo pago antes del vencimiento la racha suma uno, y si lo pago vencido la racha vuelve a 0
+    ///Ejemplo
+    ///'''
+    /// socio.registrar_puntualidad(&pago);
+    ///'''
+    fn registrar_puntualidad(&mut self, pago: &Pago){
+      if pago.esta_vencido(){
+        self.pagos_sin_atrasos = 0;
+        self.racha_descuento = 0;
+      }else{
+        self.pagos_sin_atrasos += 1;
+      }
+    }
+    ///Devuelve true si desde el ultimo descuento el socio pago a tiempo al menos "cantidad_meses" pagos seguidos
+    ///Ejemplo
+    ///'''
+    /// if socio.puede_tener_descuento(3){ ... }
+    ///'''
+    fn puede_tener_descuento(&self, cantidad_meses: u128)->bool{
+      self.pagos_sin_atrasos >= self.racha_descuento.saturating_add(cantidad_meses)
+    }
 
     ///Crea un nuevo socio y lo retorna
-    ///Al dni del socio lo obtiene por parametro, y tambien recibe señaladores para elegir la categoria y la actividad
-    ///Obtiene el costo mensual del socio, la fecha de registro, y llama a otra funcion que cree un nuevo pago pendiente
-    ///Si la categoria enviada no coinciden con las que tiene el club, tira un panic informandolo
+    ///Al id del socio lo obtiene por parametro, y tambien recibe la categoria del catalogo y la actividad elegida del registro
+    ///Si la categoria incluye todas las actividades o ninguna, el socio no guarda actividades, y si no empieza con el id de la actividad elegida
+    ///Si la categoria pide elegir una actividad y no se envio ninguna, devuelve el error ActividadInvalida
     ///Ejemplo
     ///'''
-    /// let socio = self.socio(11444666, "A".to_string(), "FUTBOL".to_string());
-    /// assert_eq!(socio.dni,11444666);
+    /// let socio = Socio::crear_socio(11444666, &categoria, Some(&actividad), timestamp, Vec::new(), Vec::new(), 0)?;
+    /// assert_eq!(socio.id,11444666);
     ///'''
-    pub fn crear_socio(dni: u128, cate: String, act:String, fecha_registro: u64, pagos_realizados: Vec<u128>, pagos_pendientes: Vec<u128>,pagos_sin_atrasos:u128)-> Socio{
-    	let categoria = match &cate as &str{
-      	"A"=> TipoCategoria::A,
-        "B"=> TipoCategoria::B,
-        "C" => TipoCategoria::C,
-        _=> panic!("No se encontro la categoria"),
+    pub fn crear_socio(id_socio: u128, categoria: &Categoria, actividad: Option<&Actividad>, fecha_registro: u64, pagos_realizados: Vec<u128>, pagos_pendientes: Vec<u128>,pagos_sin_atrasos:u128)-> Result<Socio, ClubError>{
+      let actividades = match categoria.actividades{
+        ActividadesIncluidas::Todas | ActividadesIncluidas::Cantidad(0)=> Vec::new(),
+        ActividadesIncluidas::Cantidad(_)=>[actividad.ok_or(ClubError::ActividadInvalida)?.id].to_vec(),
       };
-      let actividad = match categoria{
-        TipoCategoria::A=> Some(Actividad::TODOS),
-        TipoCategoria::B=>Socio::elegir_actividad(act),
-        TipoCategoria::C=> None,
-      };
-      Socio{
-      	dni,
-        categoria,
-        actividad,
+      Ok(Socio{
+      	id: id_socio,
+        categoria: categoria.id,
+        actividades,
         fecha_registro,
         pagos_realizados,
         pagos_pendientes,
         pagos_sin_atrasos,
+        racha_descuento: 0,
+        estado: EstadoSocio::Activo,
+        motivo_estado: None,
+      })
+    }
+  }
+  impl GrupoFamiliar{
+    ///Devuelve los socios del grupo, con el titular primero y despues los integrantes a cargo en el orden en que se sumaron
+    ///Ejemplo
+    ///'''
+    /// let socios:Vec<u128> = grupo.socios();
+    ///'''
+    fn socios(&self)->Vec<u128>{
+      let mut socios = [self.titular].to_vec();
+      socios.extend(self.integrantes.iter().copied());
+      socios
+    }
+  }
+  impl Rol{
+    ///Devuelve true si el rol tiene el permiso recibido
+    ///Ejemplo
+    ///'''
+    /// assert!(Rol::Cajero.permite(Permiso::RegistrarPagos));
+    ///'''
+    fn permite(&self, permiso: Permiso)->bool{
+      match self{
+        Rol::Owner | Rol::Administrador => true,
+        Rol::Cajero => permiso == Permiso::RegistrarPagos,
+        Rol::Auditor => permiso == Permiso::Consultar,
+        Rol::Socio => false,
       }
     }
-		///Matchea y devuelve la categoria del socio
-    ///Tira un panic en caso de que se una actividad invalida
+  }
+  impl Actividad{
+    ///Crea una actividad con el id, nombre, cuota extra y cupo maximo recibidos, activa y sin inscriptos, y la devuelve
     ///Ejemplo
     ///'''
-    /// let actividad = self.elegir_actividad("HOCKEY".to_string());
-    /// assert_eq!(Some(actividad),Actividad::HOCKEY);
+    /// let actividad = Actividad::crear_actividad(1, "FUTBOL".to_string(), None, Some(40));
     ///'''
-    fn elegir_actividad(act: String)->Option<Actividad>{
-    	let actividad = match &act as &str{
-      	"FUTBOL"=> Some(Actividad::FUTBOL),
-        "BASQUET"=> Some(Actividad::BASQUET), 
-        "RUGBY"=> Some(Actividad::RUGBY), 
-        "HOCKEY"=> Some(Actividad::HOCKEY), 
-        "NATACION"=>Some(Actividad::NATACION), 
-        "TENIS"=> Some(Actividad::TENIS),
-        "PADDLE"=> Some(Actividad::PADDLE),
-        _=> panic!("Actividad invalida"),
-      };
-      actividad
+    pub fn crear_actividad(id: u32, nombre: String, cuota_extra: Option<u128>, cupo_maximo: Option<u32>)->Actividad{
+      Actividad{
+        id,
+        nombre,
+        cuota_extra,
+        cupo_maximo,
+        inscriptos: 0,
+        activa: true,
+      }
+    }
+  }
+  impl Categoria{
+    ///Crea una categoria con el id, nombre, precio y actividades recibidos, activa, y la devuelve
+    ///Ejemplo
+    ///'''
+    /// let categoria = Categoria::crear_categoria(1, "A".to_string(), 5000, ActividadesIncluidas::Todas);
+    ///'''
+    pub fn crear_categoria(id: u32, nombre: String, precio: u128, actividades: ActividadesIncluidas)->Categoria{
+      Categoria{
+        id,
+        nombre,
+        precio,
+        actividades,
+        activa: true,
+      }
     }
-   
   }
   impl Pago{
+    ///Devuelve lo que falta pagar del pago, que es el costo mas el recargo recibido menos lo que ya se pago
+    ///Ejemplo
+    ///'''
+    /// let falta = pago.saldo(recargo);
+    ///'''
+    fn saldo(&self, recargo: u128)->u128{
+      self.costo.saturating_add(recargo).saturating_sub(self.monto_pagado)
+    }
+    ///Devuelve la cantidad de dias completos que pasaron desde el vencimiento del pago hasta la fecha recibida, o 0 si todavia no vencio
+    ///Ejemplo
+    ///'''
+    /// let dias = pago.dias_de_atraso(self.env().block_timestamp());
+    ///'''
+    fn dias_de_atraso(&self, fecha: u64)->u64{
+      fecha.saturating_sub(self.fecha_vencimiento) / (24 * 60 * 60)
+    }
     
     ///Crea un pago del socio pasado por parametro y lo devuelve
-    ///Recibe el dni del socio, el costo del pago, la fecha de vencimiento, fecha de pago, si fue pagado o no y si tiene descuento o no
+    ///Recibe el id del socio, el costo del pago, la fecha de vencimiento, fecha de pago, si fue pagado o no y si tiene descuento o no
     ///Ejemplo
     ///'''
     /// self.crear_pago(11444666, 5000, timestamp,None,true,true);
     /// 
     ///'''
-    pub fn crear_pago( dni_socio: u128,id:u128, costo: u128, fecha_vencimiento:u64,fecha_pago:Option<u64>,ok:bool,descuento:bool)->Pago{
+    pub fn crear_pago( id_socio: u128,id:u128, costo: u128, fecha_vencimiento:u64,fecha_pago:Option<u64>,ok:bool,descuento:bool)->Pago{
      
       let pagado=ok;
       let tiene_descuento=descuento;
-      let pago=Pago{
+      Pago{
       	id,
-        dni_socio,
+        id_socio,
         costo,
         fecha_vencimiento,
         fecha_pago,
         pagado,
         tiene_descuento,
-      };
-      return pago
+        recargo: 0,
+        monto_pagado: 0,
+        refinanciado: false,
+        cuota_plan: false,
+        pagador: None,
+      }
     }
     ///Nos indica si el socio pago atrasado o no
     /// Si la fecha de pago indicada es mayor a la fecha de vencimiento quiere decir que el pago es atrasado, esta vencido
@@ -885,6 +4052,21 @@ mod club{
     use ink::primitives::AccountId;
 
     use super::*;
+    use ink::storage::traits::Storable;
+
+    ///Simula la transferencia del monto y registra el pago del socio
+    fn pagar(c: &mut Club, id_socio: u128, monto: u128)->Result<(), ClubError>{
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(monto);
+      c.registro_pago(id_socio)
+    }
+    ///Le pasa el contrato a otro owner y vuelve a llamar como el owner anterior
+    fn transferir_owner(c: &mut Club, nuevo: AccountId){
+      let anterior = ink::env::caller::<ink::env::DefaultEnvironment>();
+      c.proponer_owner(nuevo).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(nuevo);
+      c.aceptar_owner().unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(anterior);
+    }
     
     #[ink::test]
     #[should_panic(expected = "Esta vencida")]
@@ -892,17 +4074,17 @@ mod club{
       //registro  socio
       let mut c=Club::new();
       let clave1=[1; 32].into();
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.crear_pagos(543);
-      c.registro_pago(543,2000);
-      let p=c.pagos.last();
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      c.crear_pagos(543).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      let p=c.pagos.get(c.cantidad_pagos);
       if let Some(g)=p{
         let vencida=g.esta_vencido();
         assert!(vencida,"Esta vencida");
@@ -913,267 +4095,244 @@ mod club{
       //registro  socio
       let mut c=Club::new();
       let clave1=[1; 32].into();
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.crear_pagos(543);
-      let mut pago:Pago;
-      let fecha_registro=000002345233;
-      let id = c.pagos.len() as u128 + 1;
-      let fecha_pago: Option<u64>;
-      fecha_pago = None;
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      c.crear_pagos(543).unwrap();
+      let fecha_registro=2345233;
+      let id = c.cantidad_pagos + 1;
+      let fecha_pago: Option<u64> = None;
       let pagado=false;
       let tiene_descuento=false;
       let fecha_vencimiento = fecha_registro + (10 * 24 * 60 * 60);
       let pago:Pago=Pago::crear_pago(543, id, 2000, fecha_vencimiento, fecha_pago, pagado, tiene_descuento);
-      assert_eq!(pago.dni_socio,543);
+      assert_eq!(pago.id_socio,543);
     }
     #[ink::test]
-    #[should_panic(expected = "No se encontro socio")]
     fn crear_pagos_socio_invalidos_test(){
       //registro  socio
       let mut c=Club::new();
       let clave1=[1; 32].into();
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.crear_pagos(44851840);
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      assert_eq!(c.crear_pagos(44851840), Err(ClubError::SocioNoEncontrado));
     }
     #[ink::test]
     fn crear_pagos_test(){
       //registro  socio
       let mut c=Club::new();
       let clave1=[1; 32].into();
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.crear_pagos(543);
-      c.crear_pagos(543);
-      c.crear_pagos(543);
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      c.crear_pagos(543).unwrap();
+      c.crear_pagos(543).unwrap();
+      c.crear_pagos(543).unwrap();
     }
     #[ink::test]
     fn crear_socio_test(){
       let pagos_realizados:Vec<u128>=Vec::new();
       let pagos_pendientes:Vec<u128>=Vec::new();
-      let socio :Socio= Socio::crear_socio(44581840, "A".to_string(), "TODOS".to_string(), 0045675675, pagos_realizados, pagos_pendientes, 10);
-      assert_eq!(socio.dni, 44581840);
+      let socio :Socio= Socio::crear_socio(44581840, &Categoria::crear_categoria(1, "A".to_string(), 5000, ActividadesIncluidas::Todas), None, 45675675, pagos_realizados, pagos_pendientes, 10).unwrap();
+      assert_eq!(socio.id, 44581840);
     }
     #[ink::test]
-
-    #[should_panic(expected = "No se encontro la categoria")]
     fn crear_socio_invalido_test(){
       let pagos_realizados:Vec<u128>=Vec::new();
       let pagos_pendientes:Vec<u128>=Vec::new();
-      let socio :Socio= Socio::crear_socio(44581840, "D".to_string(), "TODOS".to_string(), 0045675675, pagos_realizados, pagos_pendientes, 10);
-      assert_eq!(socio.dni, 44581840);
+      let categoria = Categoria::crear_categoria(2, "B".to_string(), 3000, ActividadesIncluidas::Cantidad(1));
+      let socio = Socio::crear_socio(44581840, &categoria, None, 45675675, pagos_realizados, pagos_pendientes, 10);
+      assert_eq!(socio, Err(ClubError::ActividadInvalida));
     }
     
     #[ink::test]
     fn elegir_actividad_futbol_test(){
       let pagos_realizados:Vec<u128>=Vec::new();
       let pagos_pendientes:Vec<u128>=Vec::new();
-      let socio :Socio= Socio::crear_socio(44581840, "A".to_string(), "TODOS".to_string(), 0045675675, pagos_realizados, pagos_pendientes, 10);
-      
-    	let act= Socio::elegir_actividad("FUTBOL".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::FUTBOL);
-      }
+      let _socio :Socio= Socio::crear_socio(44581840, &Categoria::crear_categoria(1, "A".to_string(), 5000, ActividadesIncluidas::Todas), None, 45675675, pagos_realizados, pagos_pendientes, 10).unwrap();
+      let c=Club::new();
+      let act = c.elegir_actividad("FUTBOL").map(|a| a.nombre);
+      assert_eq!(act, Ok("FUTBOL".to_string()));
     }
     #[ink::test]
     fn elegir_actividad_basquet_test(){
       let pagos_realizados:Vec<u128>=Vec::new();
       let pagos_pendientes:Vec<u128>=Vec::new();
-      let socio :Socio= Socio::crear_socio(44581840, "A".to_string(), "TODOS".to_string(), 0045675675, pagos_realizados, pagos_pendientes, 10);
-    	let act = Socio::elegir_actividad("BASQUET".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::BASQUET);
-      }
+      let _socio :Socio= Socio::crear_socio(44581840, &Categoria::crear_categoria(1, "A".to_string(), 5000, ActividadesIncluidas::Todas), None, 45675675, pagos_realizados, pagos_pendientes, 10).unwrap();
+      let c=Club::new();
+      let act = c.elegir_actividad("BASQUET").map(|a| a.nombre);
+      assert_eq!(act, Ok("BASQUET".to_string()));
     }
     
     #[ink::test]
     fn elegir_actividad_rugby_test(){
-      let act = Socio::elegir_actividad("RUGBY".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::RUGBY);
-      }
+      let c=Club::new();
+      let act = c.elegir_actividad("RUGBY").map(|a| a.nombre);
+      assert_eq!(act, Ok("RUGBY".to_string()));
     }
     #[ink::test]
     fn elegir_actividad_hockey_test(){
-      let act = Socio::elegir_actividad("HOCKEY".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::HOCKEY);
-      }
+      let c=Club::new();
+      let act = c.elegir_actividad("HOCKEY").map(|a| a.nombre);
+      assert_eq!(act, Ok("HOCKEY".to_string()));
     }
     #[ink::test]
     fn elegir_actividad_natacion_test(){
-
-    	let act = Socio::elegir_actividad("NATACION".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::NATACION);
-      }
+      let c=Club::new();
+      let act = c.elegir_actividad("NATACION").map(|a| a.nombre);
+      assert_eq!(act, Ok("NATACION".to_string()));
     }
     #[ink::test]
     fn elegir_actividad_tenis_test(){
-      let act = Socio::elegir_actividad("TENIS".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::TENIS);
-      }
+      let c=Club::new();
+      let act = c.elegir_actividad("TENIS").map(|a| a.nombre);
+      assert_eq!(act, Ok("TENIS".to_string()));
     }
     #[ink::test]
     fn elegir_actividad_paddle_test(){
-      let act = Socio::elegir_actividad("PADDLE".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::PADDLE);
-      }
+      let c=Club::new();
+      let act = c.elegir_actividad("PADDLE").map(|a| a.nombre);
+      assert_eq!(act, Ok("PADDLE".to_string()));
     }
     
     #[ink::test]
-    #[should_panic(expected = "Actividad invalida")]
     fn elegir_actividad_todos_test(){
-      let act = Socio::elegir_actividad("TODOS".to_string());
-      if let Some(a) = act{
-        assert_eq!(a, Actividad::TODOS);
-      }
+      let c=Club::new();
+      let act = c.elegir_actividad("TODOS");
+      assert_eq!(act, Err(ClubError::ActividadInvalida));
     }
     
     #[ink::test]
     fn get_info_socio_test(){ 
       let mut club = Club::new();
-      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
-      club.registrar_socio(44851840, "A".to_string(), "TODOS".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string()).unwrap();
+      club.registrar_socio(44851840, "A".to_string(), "TODOS".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "A","Error con categoria");
-      assert_eq!(b,"TODOS","Error con actividad");
+      assert_eq!(b,["TODOS".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_2_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "A".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "NATACION".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "A".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "NATACION".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"NATACION","Error con actividad");
+      assert_eq!(b,["NATACION".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_3_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "FUTBOL".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"FUTBOL","Error con actividad");
+      assert_eq!(b,["FUTBOL".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_4_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "C".to_string(), "FUTBOL".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "C".to_string(), "FUTBOL".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "C","Error con categoria");
-      assert_eq!(b,"NADA","Error con actividad");
+      assert_eq!(b,Vec::<String>::new(),"Error con actividad");
     }
     #[ink::test]
-
-    #[should_panic(expected = "No se encontro la categoria")]
     fn get_info_socio_5_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "D".to_string(), "FUTBOL".to_string());
-      let t=club.get_info_socio(1);
-      let a=t.0;
-      let b=t.1;
-      assert_eq!(a, "D","Error con categoria");
-      assert_eq!(b,"NADA","Error con actividad");
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      let r = club.registrar_socio(44851840, "D".to_string(), "FUTBOL".to_string());
+      assert_eq!(r, Err(ClubError::CategoriaInvalida));
+      assert_eq!(club.get_info_socio(1), Err(ClubError::PosicionInvalida));
     }
     #[ink::test]
     fn get_info_socio_6_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "BASQUET".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "BASQUET".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"BASQUET","Error con actividad");
+      assert_eq!(b,["BASQUET".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_7_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "RUGBY".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "RUGBY".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"RUGBY","Error con actividad");
+      assert_eq!(b,["RUGBY".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_8_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "HOCKEY".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "HOCKEY".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"HOCKEY","Error con actividad");
+      assert_eq!(b,["HOCKEY".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_9_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "TENIS".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "TENIS".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"TENIS","Error con actividad");
+      assert_eq!(b,["TENIS".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
     fn get_info_socio_10_test(){
       let mut club = Club::new();
-      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "PADDLE".to_string());
-      let t=club.get_info_socio(1);
+      club.registrar_socio(44581840, "C".to_string(), "TODOS".to_string()).unwrap();
+      club.registrar_socio(44851840, "B".to_string(), "PADDLE".to_string()).unwrap();
+      let t=club.get_info_socio(1).unwrap();
       let a=t.0;
       let b=t.1;
       assert_eq!(a, "B","Error con categoria");
-      assert_eq!(b,"PADDLE","Error con actividad");
+      assert_eq!(b,["PADDLE".to_string()].to_vec(),"Error con actividad");
     }
     #[ink::test]
-    #[should_panic(expected = "Actividad invalida")]
-    fn get_info_socio_nada_test(){ 
-      let mut club = Club::new();
-      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
-      club.registrar_socio(44851840, "B".to_string(), "CORRER".to_string());
-      let t=club.get_info_socio(1);
-      let a=t.0;
-      let b=t.1;
-      assert_eq!(a, "B","Error con categoria");
-     
+    fn get_info_socio_nada_test(){ 
+      let mut club = Club::new();
+      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string()).unwrap();
+      let r = club.registrar_socio(44851840, "B".to_string(), "CORRER".to_string());
+      assert_eq!(r, Err(ClubError::ActividadInvalida));
+      //no tiene que quedar ningun pago del socio que no se registro
+      assert_eq!(club.cantidad_pagos, 1);
     }
    
     #[ink::test] 
@@ -1183,17 +4342,17 @@ mod club{
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
       
       let v=c.get_socios();
-      assert_eq!(v.len(),c.socios.len(),"Esta mal");
+      assert_eq!(v.len(),c.cantidad_socios as usize,"Esta mal");
     }
     
     #[ink::test]
@@ -1203,41 +4362,40 @@ mod club{
        //mockeamos una clave
        let clave1=[1; 32].into();
        //la agregamos al vector
-       c.agregar_direccion(clave1);
-       c.registrar_socio(234, "A".to_string(), "todos".to_string());
+       c.asignar_rol(clave1, Rol::Administrador).unwrap();
+       c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
        let clave2=[2;32].into();
-       c.agregar_direccion(clave2);
-       c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+       c.asignar_rol(clave2, Rol::Administrador).unwrap();
+       c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
        let clave3=[3;32].into();
-       c.agregar_direccion(clave3);
-       c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-       c.registro_pago(178, 3000);
-       c.registro_pago(543, 2000);
-       c.registro_pago(234, 5000);
-      let t=c.get_pago(178);
+       c.asignar_rol(clave3, Rol::Administrador).unwrap();
+       c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+       pagar(&mut c, 178, 3000).unwrap();
+       pagar(&mut c, 543, 2000).unwrap();
+       pagar(&mut c, 234, 5000).unwrap();
+      let t=c.get_pago(178).unwrap();
       assert_eq!(t.len(),1);
     }
     #[ink::test]
-    #[should_panic(expected = "No existe el socio")]
-    fn get_pago_invalido_test(){
+fn get_pago_invalido_test(){
        //creamos una instancia del contrato
        let mut c=Club::new();
        //mockeamos una clave
        let clave1=[1; 32].into();
        //la agregamos al vector
-       c.agregar_direccion(clave1);
-       c.registrar_socio(234, "A".to_string(), "todos".to_string());
+       c.asignar_rol(clave1, Rol::Administrador).unwrap();
+       c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
        let clave2=[2;32].into();
-       c.agregar_direccion(clave2);
-       c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+       c.asignar_rol(clave2, Rol::Administrador).unwrap();
+       c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
        let clave3=[3;32].into();
-       c.agregar_direccion(clave3);
-       c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-       c.registro_pago(178, 3000);
-       c.registro_pago(543, 2000);
-       c.registro_pago(234, 5000);
+       c.asignar_rol(clave3, Rol::Administrador).unwrap();
+       c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+       pagar(&mut c, 178, 3000).unwrap();
+       pagar(&mut c, 543, 2000).unwrap();
+       pagar(&mut c, 234, 5000).unwrap();
       let t=c.get_pago(44851840);
-      assert_eq!(t.len(),1);
+      assert_eq!(t,Err(ClubError::SocioNoEncontrado));
     }
     
     #[ink::test]
@@ -1250,48 +4408,52 @@ mod club{
     fn existe_socio_test(){
       let mut club = Club::new();    
       let clave: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(clave);
-      club.registrar_socio(44581840, "A".to_string(), "FUTBOL".to_string());
+      club.asignar_rol(clave, Rol::Administrador).unwrap();
+      club.registrar_socio(44581840, "A".to_string(), "FUTBOL".to_string()).unwrap();
       let ok = club.existe_socio(44581840);
       assert!(ok);
     }
     #[ink::test]
-    fn set_categoria_a_test(){
+    fn crear_categoria_test(){
       let mut c=Club::new();
-      //mockeamos una clave
-      let clave1=[1; 32].into();
-      //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.set_categoria_a(7000);
-      assert_eq!(c.get_categoria_a(),Some(7000));
+      let id = c.crear_categoria("D".to_string(), 1000, ActividadesIncluidas::Cantidad(1));
+      assert_eq!(id, Ok(4));
+      assert_eq!(c.get_nombres_categorias(), ["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()].to_vec());
+      c.registrar_socio(111, "D".to_string(), "TENIS".to_string()).unwrap();
+      assert_eq!(c.get_pago(111), Ok([(1, 10 * 24 * 60 * 60, false, 1000, 0)].to_vec()));
+      assert_eq!(c.get_info_socio(0), Ok(("D".to_string(), ["TENIS".to_string()].to_vec())));
+      assert_eq!(c.crear_categoria("D".to_string(), 1, ActividadesIncluidas::Todas), Err(ClubError::CategoriaExistente));
     }
     #[ink::test]
-    fn set_categoria_b_test(){
+    fn editar_categoria_test(){
       let mut c=Club::new();
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.set_categoria_b(5000);
-      assert_eq!(c.get_categoria_b(),Some(5000));
-
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.editar_categoria(1, "A".to_string(), 7000, ActividadesIncluidas::Todas).unwrap();
+      assert_eq!(c.calcular_precio(1), Ok(7000));
+      assert_eq!(c.editar_categoria(2, "A".to_string(), 1, ActividadesIncluidas::Todas), Err(ClubError::CategoriaExistente));
+      assert_eq!(c.editar_categoria(9, "Z".to_string(), 1, ActividadesIncluidas::Todas), Err(ClubError::CategoriaInvalida));
     }
     #[ink::test]
-    fn set_categoria_c_test(){
+    fn retirar_categoria_test(){
       let mut c=Club::new();
-      //mockeamos una clave
-      let clave1=[1; 32].into();
-      //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.set_categoria_c(3000);
-      assert_eq!(c.get_categoria_c(),Some(3000));
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.retirar_categoria(3).unwrap();
+      assert_eq!(c.registrar_socio(222, "C".to_string(), "".to_string()), Err(ClubError::CategoriaInvalida));
+      assert_eq!(c.retirar_categoria(3), Err(ClubError::CategoriaInvalida));
+      //el socio que ya tenia la categoria sigue pagando su precio
+      c.crear_pagos(111).unwrap();
+      assert_eq!(c.get_pago(111).unwrap()[1].3, 2000);
+      assert!(!c.get_categorias()[2].activa);
     }
     
     #[ink::test]
     fn set_cantidad_meses_test(){
       let mut club=Club::new();
       let cant=club.cantidad_meses;
-      club.set_cantidad_meses(20);
+      club.set_cantidad_meses(20).unwrap();
       assert_ne!(club.cantidad_meses, cant);
     }
     
@@ -1299,7 +4461,7 @@ mod club{
     fn set_descuento_test(){
       let mut club=Club::new();
       let cant=club.descuento;
-      club.set_descuento(20);
+      club.set_descuento(20).unwrap();
       assert_ne!(club.descuento, cant);
     }
     
@@ -1314,58 +4476,51 @@ mod club{
     }
   
     #[ink::test]
-    fn agregar_direccion_nueva_test(){
+    fn asignar_rol_nuevo_test(){
       let mut club = Club::new();     
       let account_id: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      let resultado = club.agregar_direccion(account_id);
-      assert_eq!(resultado,true);
-    
+      let resultado = club.asignar_rol(account_id, Rol::Cajero);
+      assert_eq!(resultado,Ok(()));
+      assert_eq!(club.get_rol(account_id), Some(Rol::Cajero));
     }
     #[ink::test]
-    #[should_panic(expected = "La direccion ya esta en el vector")]
-    fn agregar_direccion_repetida_test(){
+fn asignar_rol_repetido_test(){
       let mut club = Club::new();   
       let account_id: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(account_id);
-      assert!(club.agregar_direccion(account_id),"La clave ya esta guardada");
-    }
-    #[ink::test]
-    fn eliminar_direccion_test(){
-      let mut club = Club::new();     
-      let account_id1: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(account_id1);
-      let account_id2:AccountId=[0;32].into();
-      club.agregar_direccion(account_id2);
-      club.eliminar_direccion(account_id1);
+      club.asignar_rol(account_id, Rol::Administrador).unwrap();
+      assert_eq!(club.asignar_rol(account_id, Rol::Administrador),Err(ClubError::DireccionExistente),"La clave ya tiene ese rol");
+      //cambiarle el rol si se puede
+      club.asignar_rol(account_id, Rol::Auditor).unwrap();
+      assert_eq!(club.get_roles(), [(account_id, Rol::Auditor)].to_vec());
+      assert_eq!(club.asignar_rol(account_id, Rol::Owner),Err(ClubError::RolInvalido));
     }
     #[ink::test]
-    fn eliminar_direccion_dos_test(){
+    fn quitar_rol_test(){
       let mut club = Club::new();     
       let account_id1: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(account_id1);
+      club.asignar_rol(account_id1, Rol::Administrador).unwrap();
       let account_id2:AccountId=[0;32].into();
-      club.agregar_direccion(account_id2);
-      let account_id3:AccountId=[1;32].into();
-      club.agregar_direccion(account_id3);
-      let account_id4:AccountId=[2;32].into();
-      club.agregar_direccion(account_id4);
-      club.eliminar_direccion(account_id3);
+      club.asignar_rol(account_id2, Rol::Cajero).unwrap();
+      club.quitar_rol(account_id1).unwrap();
+      assert_eq!(club.get_rol(account_id1), None);
+      assert_eq!(club.get_roles(), [(account_id2, Rol::Cajero)].to_vec());
     }
     #[ink::test]
-    #[should_panic(expected = "No esta guardada esta clave,por lo q no se puede eliminar")]
-    fn eliminar_direccion_invalida_test(){
+fn quitar_rol_invalido_test(){
       let mut club = Club::new();     
       let account_id1: AccountId = [0x42; 32].into(); // Crear un AccountId mock
    
-      club.eliminar_direccion(account_id1);
+      assert_eq!(club.quitar_rol(account_id1),Err(ClubError::DireccionNoEncontrada));
     }
     #[ink::test]
-    fn esta_permitido_test(){
+    fn tiene_permiso_test(){
       let mut club = Club::new();   
       let account_id: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(account_id);
-      let okey = club.esta_permitido();
-      assert!(okey,"No esta permitida la clave");
+      club.asignar_rol(account_id, Rol::Administrador).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(account_id);
+      assert!(club.tiene_permiso(Permiso::Administrar),"No esta permitida la clave");
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x43; 32].into());
+      assert!(!club.tiene_permiso(Permiso::Consultar));
     }
     
     #[ink::test]
@@ -1373,33 +4528,64 @@ mod club{
       let mut club=Club::new();
       assert!(club.es_owner());
       let clave: AccountId = [0x42; 32].into();
-      club.set_owner(clave);
-      assert_eq!(club.es_owner(), false);
+      transferir_owner(&mut club, clave);
+      assert!(!club.es_owner());
     }
     #[ink::test]
-    fn cambiar_politica_test(){
-      let mut club=Club::new();
-      assert!(club.politica);
-      club.cambiar_politica();
-      assert_eq!(club.politica, false);
+    fn get_rol_owner_test(){
+      let club=Club::new();
+      assert_eq!(club.get_rol([0x01; 32].into()), Some(Rol::Owner));
+      assert_eq!(club.get_rol([0x42; 32].into()), None);
     }
     #[ink::test]
-    fn aceptar_modificaciones_test(){
+    fn permisos_owner_test(){
       let mut club=Club::new();
-      assert!(club.aceptar_modificaciones());
+      assert!(club.tiene_permiso(Permiso::Administrar));
       let clave: AccountId = [0x42; 32].into();
-      club.set_owner(clave);
-      assert_eq!(club.es_owner(), false);
+      transferir_owner(&mut club, clave);
+      assert!(!club.es_owner());
+      assert!(!club.tiene_permiso(Permiso::Consultar));
     }
     
     #[ink::test]
-    fn set_owner_test(){
+    fn aceptar_owner_test(){
       let mut club=Club::new();
       let clave: AccountId = [0x42; 32].into();
-      club.set_owner(clave);
+      club.proponer_owner(clave).unwrap();
+      //hasta que no acepta, el owner sigue siendo el mismo
+      assert!(club.es_owner());
+      assert_eq!(club.get_propuesta_owner(), Some((clave, 7 * 24 * 60 * 60)));
+      assert_eq!(club.aceptar_owner(), Err(ClubError::NoAutorizado));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(clave);
+      club.aceptar_owner().unwrap();
       if let Some(owner) = club.owner{
         assert_eq!(owner, clave);
       }
+      assert_eq!(club.get_propuesta_owner(), None);
+      assert_eq!(club.aceptar_owner(), Err(ClubError::SinPropuestaPendiente));
+    }
+    #[ink::test]
+    fn propuesta_owner_vencida_test(){
+      let mut club=Club::new();
+      let clave: AccountId = [0x42; 32].into();
+      club.proponer_owner(clave).unwrap();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(8 * 24 * 60 * 60);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(clave);
+      assert_eq!(club.aceptar_owner(), Err(ClubError::PropuestaVencida));
+      assert_eq!(club.proponer_owner(clave), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn cancelar_propuesta_test(){
+      let mut club=Club::new();
+      let clave: AccountId = [0x42; 32].into();
+      assert_eq!(club.cancelar_propuesta(), Err(ClubError::SinPropuestaPendiente));
+      club.proponer_owner(clave).unwrap();
+      club.cancelar_propuesta().unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(clave);
+      assert_eq!(club.aceptar_owner(), Err(ClubError::SinPropuestaPendiente));
+      let eventos = eventos_emitidos();
+      assert!(matches!(&eventos[0], Event::OwnerPropuesto(e) if e.propuesto == clave));
+      assert!(matches!(&eventos[1], Event::PropuestaOwnerCancelada(e) if e.propuesto == clave));
     }
     #[ink::test]
     fn guardar_firma_test(){
@@ -1411,91 +4597,79 @@ mod club{
     fn crear_pago_pendiente_test(){
       let mut club=Club::new();
       club.crear_pago_pendiente(44666785, 5000, 334545654);
-      assert_eq!(club.pagos.len(), 1);
+      assert_eq!(club.cantidad_pagos, 1);
     }
     
     #[ink::test]
     fn calcular_precio_a_test(){
       let c=Club::new();
-      let precio = c.calcular_precio(&TipoCategoria::A);
-      assert_eq!(precio, 5000);
+      let precio = c.calcular_precio(1);
+      assert_eq!(precio, Ok(5000));
     }
     #[ink::test]
     fn calcular_precio_b_test(){
       let c=Club::new();
-      let precio = c.calcular_precio(&TipoCategoria::B);
-      assert_eq!(precio, 3000);
+      let precio = c.calcular_precio(2);
+      assert_eq!(precio, Ok(3000));
     }
     #[ink::test]
     fn calcular_precio_c_test(){
       let c=Club::new();
-      let precio = c.calcular_precio(&TipoCategoria::C);
-      assert_eq!(precio, 2000);
+      let precio = c.calcular_precio(3);
+      assert_eq!(precio, Ok(2000));
     }
     
     #[ink::test]
     fn buscar_socio_test(){
       let mut club = Club::new();    
      let clave: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-     club.agregar_direccion(clave);
-     club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
+     club.asignar_rol(clave, Rol::Administrador).unwrap();
+     club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string()).unwrap();
      let o=club.buscar_socio(44581840);
-     let mut ok=false;
-     if let Some(s)=o{
-       ok=true;
-      };
-     assert_eq!(ok,true,"no se encontro el socio");
+     assert!(o.is_some(),"no se encontro el socio");
     }
     #[ink::test]
     fn buscar_socio_invalido_test(){
       let mut club = Club::new();    
      let clave: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-     club.agregar_direccion(clave);
-     club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
+     club.asignar_rol(clave, Rol::Administrador).unwrap();
+     club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string()).unwrap();
      let o=club.buscar_socio(44851240);
-     let mut ok=false;
-     if let Some(s)=o{
-       ok=true;
-      };
-     assert_eq!(ok,false,"no se encontro el socio");
+     assert!(o.is_none(),"se encontro un socio que no existe");
     }
     #[ink::test]
     fn primero_pagos_pendientes_test(){
       let mut club=Club::new();
-      club.registrar_socio(44851840,"B".to_string(),"NATACION".to_string());
-      club.registrar_socio(22884342,"C".to_string(),"TENIS".to_string());
-      let a=club.primero_pagos_pendientes(0 as usize);
-      let mut ok=false;
-      if let Some(c)=a{
-        ok=true;
-      }
-      assert!(ok);
+      club.registrar_socio(44851840,"B".to_string(),"NATACION".to_string()).unwrap();
+      club.registrar_socio(22884342,"C".to_string(),"TENIS".to_string()).unwrap();
+      let socio=club.buscar_socio(44851840).unwrap();
+      let a=club.primero_pagos_pendientes(&socio);
+      assert!(a.is_some());
     }
     #[ink::test]
     fn registrar_socio_nuevo_test(){
       let mut club = Club::new();    
       let clave: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(clave);
-      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
-      let num = club.socios.len();
+      club.asignar_rol(clave, Rol::Administrador).unwrap();
+      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string()).unwrap();
+      let num = club.cantidad_socios;
       assert_eq!(num, 1);
     }
     #[ink::test]
-    #[should_panic(expected = "La direccion ya esta en el vector")]
-    fn registrar_socio_invalido_test(){
+fn registrar_socio_invalido_test(){
       let mut club = Club::new(); 
       let clave: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(clave);
-      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
+      club.asignar_rol(clave, Rol::Administrador).unwrap();
+      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string()).unwrap();
       
       let clave2: AccountId = [0x42 ; 32].into(); // Crear un AccountId mock
-      club.agregar_direccion(clave2);
-      club.registrar_socio(11222333, "B".to_string(), "BASQUET".to_string());
+      assert_eq!(club.asignar_rol(clave2, Rol::Administrador),Err(ClubError::DireccionExistente));
+      club.registrar_socio(11222333, "B".to_string(), "BASQUET".to_string()).unwrap();
       
-      let clave3: AccountId = [0x42; 32].into(); // Crear un AccountId mock
-      club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
+      let r = club.registrar_socio(44581840, "B".to_string(), "NATACION".to_string());
+      assert_eq!(r,Err(ClubError::SocioExistente));
       
-      let num = club.socios.len();
+      let num = club.cantidad_socios;
       assert_eq!(num, 2);
     }
     
@@ -1507,79 +4681,178 @@ mod club{
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.registro_pago(178, 3000);
-      c.registro_pago(543, 2000);
-      c.registro_pago(234, 5000);
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      pagar(&mut c, 178, 3000).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      pagar(&mut c, 234, 5000).unwrap();
      
   
     }
 
     #[ink::test]
-    #[should_panic(expected = "No paga lo q corresponde para su categoria")]
-    fn registro_pago_monto_invalido_test(){
+fn registro_pago_monto_invalido_test(){
       //creamos una instancia del contrato
       let mut c=Club::new();
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
       
-      c.registro_pago(178, 3000);
+      pagar(&mut c, 178, 3000).unwrap();
       
-      c.registro_pago(543, 3000);
+      assert_eq!(pagar(&mut c, 543, 0),Err(ClubError::MontoIncorrecto));
+    }
+    #[ink::test]
+    fn registro_pago_suma_tesoreria_test(){
+      let mut c=Club::new();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      pagar(&mut c, 234, 5000).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(c.tesoreria, 7000);
+    }
+    #[ink::test]
+    fn registro_pago_excedente_a_saldo_test(){
+      let contrato: AccountId = [0xFF; 32].into();
+      let caller: AccountId = [0x01; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 0);
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(2500);
+      c.registro_pago(543).unwrap();
+      let saldo_contrato = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contrato);
+      let saldo_caller = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(caller);
+      //el excedente no se devuelve, queda como saldo a favor del socio
+      assert_eq!(saldo_contrato, Ok(2500));
+      assert_eq!(saldo_caller, Ok(1_000_000 - 2500));
+      assert_eq!(c.tesoreria, 2500);
+      assert_eq!(c.get_saldo_a_favor(543), Ok(500));
+    }
+    #[ink::test]
+    fn excedente_de_cajero_a_saldo_del_socio_test(){
+      let mut c=Club::new();
+      let cajero: AccountId = [0x10; 32].into();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      c.asignar_rol(cajero, Rol::Cajero).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cajero);
+      pagar(&mut c, 543, 3000).unwrap();
+      //el excedente que transfiere el cajero es del socio, y no queda ningun reembolso para el cajero
+      assert_eq!(c.reembolsos_pendientes.get(cajero), None);
+      assert_eq!(c.reclamar_reembolso(), Err(ClubError::SinReembolsosPendientes));
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::SaldoAcreditado(e)) if e.id_socio == 543 && e.caller == cajero && e.monto == 1000));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x01; 32].into());
+      assert_eq!(c.get_saldo_a_favor(543), Ok(1000));
+      //y se usa en el proximo pago del socio
+      c.crear_pagos(543).unwrap();
+      assert_eq!(c.pagos.get(2).unwrap().monto_pagado, 1000);
+    }
+    #[ink::test]
+    fn retirar_fondos_test(){
+      let contrato: AccountId = [0xFF; 32].into();
+      let destino: AccountId = [0x02; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 0);
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(2000);
+      c.registro_pago(543).unwrap();
+      c.retirar_fondos(1500, destino).unwrap();
+      let saldo_destino = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(destino);
+      assert_eq!(saldo_destino, Ok(1000 + 1500));
+      assert_eq!(c.balance_tesoreria(), 500);
+      let retiros = c.get_retiros();
+      assert_eq!(retiros.len(), 1);
+      assert_eq!(retiros[0].destino, destino);
+      assert_eq!(retiros[0].monto, 1500);
+    }
+    #[ink::test]
+fn retirar_fondos_insuficientes_test(){
+      let contrato: AccountId = [0xFF; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 0);
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(2000);
+      c.registro_pago(543).unwrap();
+      assert_eq!(c.retirar_fondos(2001, [0x02; 32].into()),Err(ClubError::FondosInsuficientes));
+    }
+    #[ink::test]
+fn retirar_fondos_no_owner_test(){
+      let mut c=Club::new();
+      let otro: AccountId = [0x02; 32].into();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(otro);
+      assert_eq!(c.retirar_fondos(1, otro),Err(ClubError::NoAutorizado));
     }
     #[ink::test]
-    #[should_panic(expected = "El socio no existe")]
-    fn registro_pago_socio_invalido(){
+    fn reembolso_pendiente_test(){
+      //el contrato no tiene fondos, asi que la devolucion del excedente a la cuenta del socio al darse de baja falla y queda pendiente
+      let contrato: AccountId = [0xEE; 32].into();
+      let cuenta: AccountId = [0x42; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      vincular(&mut c, 543, cuenta);
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2500);
+      c.dar_de_baja(543).unwrap();
+      assert_eq!(c.total_reembolsos_pendientes, 500);
+      assert_eq!(c.reembolsos_pendientes.get(cuenta), Some(500));
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 2200);
+      assert_eq!(c.fondos_disponibles(), 1700);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.reclamar_reembolso(), Ok(500));
+      assert_eq!(c.total_reembolsos_pendientes, 0);
+      assert_eq!(c.fondos_disponibles(), 1700);
+    }
+    #[ink::test]
+fn registro_pago_socio_invalido(){
       //creamos una instancia del contrato
       let mut c=Club::new();
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.registro_pago(44851840, 3000);
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      assert_eq!(pagar(&mut c, 44851840, 3000),Err(ClubError::SocioNoEncontrado));
     }
     #[ink::test]
-    #[should_panic(expected = "No tiene pagos pendientes el socio")]
-    fn registro_pago_sin_pendientes(){
+fn registro_pago_sin_pendientes(){
       //creamos una instancia del contrato
       let mut c=Club::new();
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      c.registro_pago(543, 2000);
-      c.registro_pago(543,2000);
-      c.registro_pago(543,2000);
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(pagar(&mut c, 543, 2000),Err(ClubError::SinPagosPendientes));
     }
 
     
@@ -1590,18 +4863,18 @@ mod club{
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
       let clave2=[2;32].into();
-      c.agregar_direccion(clave2);
-      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string());
+      c.asignar_rol(clave2, Rol::Administrador).unwrap();
+      c.registrar_socio(178, "B".to_string(), "FUTBOL".to_string()).unwrap();
       let clave3=[3;32].into();
-      c.agregar_direccion(clave3);
-      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string());
-      let dni=Some(543);
-      let ad=c.consultar_pagos(dni);
-      assert_eq!(ad.dni,dni);
-      assert_eq!(ad.cate,Some(TipoCategoria::C));
+      c.asignar_rol(clave3, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      let id_socio=Some(543);
+      let ad=c.consultar_pagos(id_socio).unwrap();
+      assert_eq!(ad.id_socio,id_socio);
+      assert_eq!(ad.cate,Some("C".to_string()));
     }
     
     #[ink::test]
@@ -1611,29 +4884,1557 @@ mod club{
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
-      let dni=None;
-      let ad=c.consultar_pagos(dni);
-      assert_eq!(ad.dni,dni);
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
+      let id_socio=None;
+      let ad=c.consultar_pagos(id_socio).unwrap();
+      assert_eq!(ad.id_socio,id_socio);
       assert_eq!(ad.cate,None);
     }
     #[ink::test]
-    #[should_panic(expected = "No se encontro el socio")]
-    fn consultar_pago_invalido(){
+fn consultar_pago_invalido(){
       
       //creamos una instancia del contrato
       let mut c=Club::new();
       //mockeamos una clave
       let clave1=[1; 32].into();
       //la agregamos al vector
-      c.agregar_direccion(clave1);
-      c.registrar_socio(234, "A".to_string(), "todos".to_string());
-      let dni=Some(44851840);
-      let ad=c.consultar_pagos(dni);
-      assert_eq!(ad.dni,dni);
-      assert_eq!(ad.cate,Some(TipoCategoria::C));
+      c.asignar_rol(clave1, Rol::Administrador).unwrap();
+      c.registrar_socio(234, "A".to_string(), "todos".to_string()).unwrap();
+      let id_socio=Some(44851840);
+      let ad=c.consultar_pagos(id_socio);
+      assert!(matches!(ad, Err(ClubError::SocioNoEncontrado)));
     }
-  }
-} 
+    type Event = <Club as ::ink::reflect::ContractEventBase>::Type;
 
+    ///Decodifica todos los eventos que se emitieron hasta el momento
+    fn eventos_emitidos()->Vec<Event>{
+      ink::env::test::recorded_events()
+        .map(|e| <Event as scale::Decode>::decode(&mut &e.data[..]).unwrap())
+        .collect()
+    }
+    #[ink::test]
+    fn eventos_registrar_socio_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      let eventos = eventos_emitidos();
+      assert_eq!(eventos.len(), 2);
+      let caller: AccountId = [0x01; 32].into();
+      if let Event::SocioRegistrado(e) = &eventos[0]{
+        assert_eq!(e.id_socio, 543);
+        assert_eq!(e.caller, caller);
+        assert_eq!(e.categoria, 3);
+      }else{panic!("Se esperaba SocioRegistrado")}
+      if let Event::PagoCreado(e) = &eventos[1]{
+        assert_eq!(e.id_socio, 543);
+        assert_eq!(e.id, 1);
+        assert_eq!(e.costo, 2000);
+      }else{panic!("Se esperaba PagoCreado")}
+    }
+    #[ink::test]
+    fn eventos_registro_pago_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      let eventos = eventos_emitidos();
+      assert_eq!(eventos.len(), 3);
+      if let Event::PagoRegistrado(e) = &eventos[2]{
+        assert_eq!(e.id_socio, 543);
+        assert_eq!(e.id, 1);
+        assert_eq!(e.monto, 2000);
+      }else{panic!("Se esperaba PagoRegistrado")}
+    }
+    #[ink::test]
+    fn eventos_descuento_aplicado_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "NINGUNA".to_string()).unwrap();
+      c.set_cantidad_meses(1).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      c.crear_pagos(543).unwrap();
+      let eventos = eventos_emitidos();
+      if let Some(Event::DescuentoAplicado(e)) = eventos.last(){
+        assert_eq!(e.id_socio, 543);
+        assert_eq!(e.id, 2);
+        assert_eq!(e.descuento, 600);
+      }else{panic!("Se esperaba DescuentoAplicado")}
+      assert!(matches!(eventos[eventos.len() - 2], Event::PagoCreado(_)));
+    }
+    #[ink::test]
+    fn eventos_configuracion_test(){
+      let mut c=Club::new();
+      let clave: AccountId = [0x42; 32].into();
+      c.editar_categoria(1, "A".to_string(), 7000, ActividadesIncluidas::Todas).unwrap();
+      c.set_descuento(20).unwrap();
+      c.asignar_rol(clave, Rol::Cajero).unwrap();
+      c.quitar_rol(clave).unwrap();
+      transferir_owner(&mut c, clave);
+      let eventos = eventos_emitidos();
+      assert_eq!(eventos.len(), 6);
+      assert!(matches!(&eventos[0], Event::CategoriaEditada(e) if e.id == 1 && e.precio == 7000));
+      assert!(matches!(&eventos[1], Event::DescuentoCambiado(e) if e.descuento == 20));
+      assert!(matches!(&eventos[2], Event::RolAsignado(e) if e.direccion == clave && e.rol == Rol::Cajero));
+      assert!(matches!(&eventos[3], Event::RolQuitado(e) if e.direccion == clave));
+      assert!(matches!(&eventos[4], Event::OwnerPropuesto(e) if e.propuesto == clave));
+      assert!(matches!(&eventos[5], Event::OwnerCambiado(e) if e.nuevo == clave));
+    }
+    #[ink::test]
+    fn eventos_no_se_emiten_sin_permiso_test(){
+      let mut c=Club::new();
+      let otro: AccountId = [0x02; 32].into();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(otro);
+      assert_eq!(c.proponer_owner(otro), Err(ClubError::NoAutorizado));
+      assert_eq!(c.asignar_rol(otro, Rol::Administrador), Err(ClubError::NoAutorizado));
+      assert_eq!(c.editar_categoria(2, "B".to_string(), 1, ActividadesIncluidas::Cantidad(1)), Err(ClubError::NoAutorizado));
+      assert_eq!(eventos_emitidos().len(), 0);
+    }
+    #[ink::test]
+    fn pagos_por_socio_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      c.crear_pagos(111).unwrap();
+      c.crear_pagos(222).unwrap();
+      c.crear_pagos(111).unwrap();
+      assert_eq!(c.cantidad_pagos, 5);
+      assert_eq!(c.pagos_socio.get(111), Some([1,3,5].to_vec()));
+      assert_eq!(c.pagos_socio.get(222), Some([2,4].to_vec()));
+      let ids:Vec<u128> = c.get_pago(222).unwrap().iter().map(|p| p.0).collect();
+      assert_eq!(ids, [2,4].to_vec());
+      assert_eq!(c.get_socios(), [(111, EstadoSocio::Activo),(222, EstadoSocio::Activo)].to_vec());
+      assert_eq!(c.get_info_socio(1), Ok(("C".to_string(), Vec::new())));
+    }
+    #[ink::test]
+    fn migrar_datos_test(){
+      let mut c=Club::new();
+      let socio = Socio::crear_socio(333, &Categoria::crear_categoria(2, "B".to_string(), 3000, ActividadesIncluidas::Cantidad(1)), Some(&Actividad::crear_actividad(6, "TENIS".to_string(), None, None)), 0, [1].to_vec(), [2].to_vec(), 1).unwrap();
+      let pagos = Vec::from([
+        Pago::crear_pago(333, 2, 3000, 2000, None, false, false),
+        Pago::crear_pago(333, 1, 3000, 1000, Some(500), true, false),
+      ]);
+      c.migrar_datos(Vec::from([socio]), pagos).unwrap();
+      //los registros ya vienen con el id del socio, y se guardan con ese id
+      let id = 333;
+      assert_eq!(c.get_socios(), [(id, EstadoSocio::Activo)].to_vec());
+      assert_eq!(c.get_pago(id), Ok([(1,1000,true,3000,0),(2,2000,false,3000,0)].to_vec()));
+      assert_eq!(c.get_info_socio(0), Ok(("B".to_string(), ["TENIS".to_string()].to_vec())));
+      assert_eq!(c.actividades.get(6).unwrap().inscriptos, 1);
+      //los pagos nuevos siguen con el id siguiente a los migrados
+      c.crear_pagos(id).unwrap();
+      assert_eq!(c.cantidad_pagos, 3);
+      assert_eq!(c.buscar_socio(id).unwrap().pagos_pendientes.first().copied(), Some(2));
+      assert_eq!(pagar(&mut c, id, 3000), Ok(()));
+      assert_eq!(c.buscar_socio(id).unwrap().pagos_pendientes.first().copied(), Some(3));
+    }
+    #[ink::test]
+    fn migrar_datos_invalidos_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      let repetido = Socio::crear_socio(111, &Categoria::crear_categoria(1, "A".to_string(), 5000, ActividadesIncluidas::Todas), None, 0, Vec::new(), Vec::new(), 0).unwrap();
+      assert_eq!(c.migrar_datos(Vec::from([repetido]), Vec::new()), Err(ClubError::SocioExistente));
+      let pago = Pago::crear_pago(111, 1, 5000, 0, None, false, false);
+      assert_eq!(c.migrar_datos(Vec::new(), Vec::from([pago])), Err(ClubError::PagoExistente));
+      let sin_socio = Pago::crear_pago(999, 7, 5000, 0, None, false, false);
+      assert_eq!(c.migrar_datos(Vec::new(), Vec::from([sin_socio])), Err(ClubError::SocioNoEncontrado));
+      assert_eq!(c.cantidad_socios, 1);
+      assert_eq!(c.cantidad_pagos, 1);
+    }
+    #[ink::test]
+    fn migrar_datos_no_owner_test(){
+      let mut c=Club::new();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x02; 32].into());
+      assert_eq!(c.migrar_datos(Vec::new(), Vec::new()), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn facturar_periodo_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      //todavia no vencio ningun pago
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 2);
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10 * 24 * 60 * 60);
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 4);
+      assert_eq!(c.get_pago(222).unwrap()[1], (4, 40 * 24 * 60 * 60, false, 2000, 0));
+      //en el mismo periodo no se crean pagos repetidos
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 4);
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(40 * 24 * 60 * 60);
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 6);
+    }
+    #[ink::test]
+    fn facturar_periodo_dos_veces_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(333, "C".to_string(), "".to_string()).unwrap();
+      //sin la categoria B no se le puede crear el pago a 222, pero se factura a los demas
+      c.categorias.remove(2);
+      //a los 100 dias deben tres meses, pero en el periodo se les crea un solo pago
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100 * 24 * 60 * 60);
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 5);
+      let eventos = eventos_emitidos();
+      assert!(eventos.iter().any(|e| matches!(e, Event::SocioNoFacturado(e) if e.id_socio == 222 && e.error == ClubError::CategoriaInvalida)));
+      assert!(matches!(eventos.last(), Some(Event::FacturacionProcesada(e)) if e.creados == 2 && e.fallidos == 1 && e.terminado));
+      //volver a llamarla en el mismo periodo no crea pagos repetidos, aunque el pago nuevo tambien este vencido
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 5);
+      assert_eq!(c.get_pago(111).unwrap().len(), 2);
+      //en el periodo siguiente se les crea el proximo
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(120 * 24 * 60 * 60);
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cantidad_pagos, 7);
+      assert_eq!(c.get_pago(111).unwrap()[2].1, 70 * 24 * 60 * 60);
+    }
+    #[ink::test]
+    fn facturar_periodo_por_tandas_test(){
+      let mut c=Club::new();
+      c.set_limite_facturacion(2).unwrap();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(333, "C".to_string(), "".to_string()).unwrap();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10 * 24 * 60 * 60);
+      assert_eq!(c.facturar_periodo(), Ok(false));
+      assert_eq!(c.cursor_facturacion, 2);
+      assert_eq!(c.cantidad_pagos, 5);
+      assert_eq!(c.facturar_periodo(), Ok(true));
+      assert_eq!(c.cursor_facturacion, 0);
+      assert_eq!(c.cantidad_pagos, 6);
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::FacturacionProcesada(e)) if e.desde == 2 && e.hasta == 3 && e.creados == 1 && e.terminado));
+    }
+    #[ink::test]
+    fn facturar_periodo_no_autorizado_test(){
+      let mut c=Club::new();
+      assert_eq!(c.set_limite_facturacion(0), Err(ClubError::LimiteInvalido));
+      assert_eq!(c.limite_facturacion, 50);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x02; 32].into());
+      assert_eq!(c.facturar_periodo(), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn crear_actividad_test(){
+      let mut c=Club::new();
+      assert_eq!(c.crear_actividad("VOLEY".to_string(), Some(500), Some(1)), Ok(8));
+      assert_eq!(c.get_nombres_actividades().last(), Some(&"VOLEY".to_string()));
+      c.registrar_socio(111, "B".to_string(), "VOLEY".to_string()).unwrap();
+      assert_eq!(c.get_info_socio(0), Ok(("B".to_string(), ["VOLEY".to_string()].to_vec())));
+      //el cupo maximo es de un socio
+      assert_eq!(c.registrar_socio(222, "B".to_string(), "VOLEY".to_string()), Err(ClubError::CupoCompleto));
+      assert_eq!(c.crear_actividad("VOLEY".to_string(), None, None), Err(ClubError::ActividadExistente));
+      assert_eq!(c.crear_actividad("TODOS".to_string(), None, None), Err(ClubError::ActividadInvalida));
+    }
+    #[ink::test]
+    fn editar_actividad_test(){
+      let mut c=Club::new();
+      c.editar_actividad(1, "FUTBOL 5".to_string(), Some(300), Some(10)).unwrap();
+      let futbol = c.get_actividades()[0].clone();
+      assert_eq!(futbol.nombre, "FUTBOL 5".to_string());
+      assert_eq!(futbol.cuota_extra, Some(300));
+      assert_eq!(futbol.cupo_maximo, Some(10));
+      assert_eq!(c.editar_actividad(1, "TENIS".to_string(), None, None), Err(ClubError::ActividadExistente));
+      assert_eq!(c.editar_actividad(99, "VOLEY".to_string(), None, None), Err(ClubError::ActividadInvalida));
+    }
+    #[ink::test]
+    fn retirar_actividad_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "B".to_string(), "PADDLE".to_string()).unwrap();
+      c.retirar_actividad(7).unwrap();
+      assert_eq!(c.registrar_socio(222, "B".to_string(), "PADDLE".to_string()), Err(ClubError::ActividadInvalida));
+      assert_eq!(c.retirar_actividad(7), Err(ClubError::ActividadInvalida));
+      //el socio que ya la tenia la mantiene
+      assert_eq!(c.get_info_socio(0), Ok(("B".to_string(), ["PADDLE".to_string()].to_vec())));
+    }
+    #[ink::test]
+    fn agregar_actividad_test(){
+      let mut c=Club::new();
+      c.crear_categoria("D".to_string(), 4000, ActividadesIncluidas::Cantidad(2)).unwrap();
+      c.editar_actividad(6, "TENIS".to_string(), Some(500), None).unwrap();
+      c.editar_actividad(7, "PADDLE".to_string(), Some(300), None).unwrap();
+      c.registrar_socio(111, "D".to_string(), "TENIS".to_string()).unwrap();
+      //el primer pago ya incluye la cuota extra de la actividad elegida
+      assert_eq!(c.get_pago(111).unwrap()[0].3, 4500);
+      c.agregar_actividad(111, "PADDLE".to_string()).unwrap();
+      assert_eq!(c.get_info_socio(0), Ok(("D".to_string(), ["TENIS".to_string(), "PADDLE".to_string()].to_vec())));
+      assert_eq!(c.actividades.get(7).unwrap().inscriptos, 1);
+      //la actividad agregada se cobra en el proximo pago
+      c.crear_pagos(111).unwrap();
+      assert_eq!(c.get_pago(111).unwrap()[1].3, 4800);
+      assert_eq!(c.agregar_actividad(111, "FUTBOL".to_string()), Err(ClubError::LimiteActividades));
+    }
+    #[ink::test]
+    fn agregar_actividad_invalida_test(){
+      let mut c=Club::new();
+      c.crear_categoria("D".to_string(), 4000, ActividadesIncluidas::Cantidad(3)).unwrap();
+      c.registrar_socio(111, "D".to_string(), "TENIS".to_string()).unwrap();
+      c.registrar_socio(222, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(333, "C".to_string(), "".to_string()).unwrap();
+      assert_eq!(c.agregar_actividad(111, "TENIS".to_string()), Err(ClubError::YaInscripto));
+      assert_eq!(c.agregar_actividad(111, "AJEDREZ".to_string()), Err(ClubError::ActividadInvalida));
+      assert_eq!(c.agregar_actividad(222, "TENIS".to_string()), Err(ClubError::LimiteActividades));
+      assert_eq!(c.agregar_actividad(333, "TENIS".to_string()), Err(ClubError::LimiteActividades));
+      assert_eq!(c.agregar_actividad(999, "TENIS".to_string()), Err(ClubError::SocioNoEncontrado));
+    }
+    #[ink::test]
+    fn quitar_actividad_test(){
+      let mut c=Club::new();
+      c.editar_actividad(1, "FUTBOL".to_string(), Some(1000), None).unwrap();
+      c.registrar_socio(111, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      assert_eq!(c.get_pago(111).unwrap()[0].3, 4000);
+      c.quitar_actividad(111, "FUTBOL".to_string()).unwrap();
+      assert_eq!(c.get_info_socio(0), Ok(("B".to_string(), Vec::new())));
+      assert_eq!(c.actividades.get(1).unwrap().inscriptos, 0);
+      c.crear_pagos(111).unwrap();
+      assert_eq!(c.get_pago(111).unwrap()[1].3, 3000);
+      assert_eq!(c.quitar_actividad(111, "FUTBOL".to_string()), Err(ClubError::NoInscripto));
+      //ahora puede elegir otra actividad
+      c.agregar_actividad(111, "HOCKEY".to_string()).unwrap();
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::ActividadAgregada(e)) if e.id_socio == 111 && e.actividad == 4));
+    }
+    #[ink::test]
+    fn cambiar_categoria_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.crear_pagos(111).unwrap();
+      pagar(&mut c, 111, 2000).unwrap();
+      //el segundo pago vence a los 40 dias, asi que le quedan 10 de los 30 dias del periodo
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(30 * 24 * 60 * 60);
+      c.cambiar_categoria(111, "A".to_string()).unwrap();
+      //2000 - 2000*10/30 + 5000*10/30
+      assert_eq!(c.get_pago(111).unwrap()[1].3, 2000 - 666 + 1666);
+      assert_eq!(c.get_info_socio(0), Ok(("A".to_string(), ["TODOS".to_string()].to_vec())));
+      let historial = c.get_historial_categorias(111).unwrap();
+      assert_eq!(historial.len(), 1);
+      assert_eq!(historial[0].anterior, 3);
+      assert_eq!(historial[0].nueva, 1);
+      assert_eq!(historial[0].costo_anterior, Some(2000));
+      assert_eq!(historial[0].costo_nuevo, Some(3000));
+      //los pagos siguientes ya tienen el precio nuevo
+      c.crear_pagos(111).unwrap();
+      assert_eq!(c.get_pago(111).unwrap()[2].3, 5000);
+    }
+    #[ink::test]
+    fn cambiar_categoria_con_descuento_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.set_cantidad_meses(1).unwrap();
+      pagar(&mut c, 111, 2000).unwrap();
+      //el segundo pago sale 1400 con el 30% de descuento y vence a los 40 dias
+      c.crear_pagos(111).unwrap();
+      assert_eq!(c.pagos.get(2).unwrap().costo, 1400);
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(30 * 24 * 60 * 60);
+      c.cambiar_categoria(111, "A".to_string()).unwrap();
+      //la cuota nueva lleva el mismo descuento, 5000*1400/2000 = 3500: 1400 - 1400*10/30 + 3500*10/30
+      assert_eq!(c.pagos.get(2).unwrap().costo, 1400 - 466 + 1166);
+      let historial = c.get_historial_categorias(111).unwrap();
+      assert_eq!((historial[0].costo_anterior, historial[0].costo_nuevo), (Some(1400), Some(2100)));
+    }
+    #[ink::test]
+    fn cambiar_categoria_pago_parcial_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      pagar(&mut c, 111, 4500).unwrap();
+      //al primer pago le quedan 10 de los 30 dias: 5000 - 5000*10/30 + 2000*10/30 = 4000, que ya esta cubierto por lo pagado
+      c.cambiar_categoria(111, "C".to_string()).unwrap();
+      let pago = c.pagos.get(1).unwrap();
+      assert!(pago.pagado);
+      assert_eq!((pago.costo, pago.monto_pagado), (4000, 4000));
+      assert!(c.buscar_socio(111).unwrap().pagos_pendientes.is_empty());
+      assert_eq!(c.buscar_socio(111).unwrap().pagos_realizados, [1].to_vec());
+      assert_eq!(c.get_saldo_a_favor(111), Ok(500));
+      let eventos = eventos_emitidos();
+      assert!(eventos.iter().any(|e| matches!(e, Event::PagoRegistrado(e) if e.id == 1 && e.monto == 4000)));
+      assert!(matches!(eventos.last(), Some(Event::SaldoAcreditado(e)) if e.id_socio == 111 && e.monto == 500));
+    }
+    #[ink::test]
+    fn cambiar_categoria_plan_cuotas_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.crear_plan_cuotas(111, 2, 10).unwrap();
+      let antes = c.get_pago(111).unwrap();
+      //las cuotas del plan y el pago refinanciado no se prorratean
+      c.cambiar_categoria(111, "A".to_string()).unwrap();
+      assert_eq!(c.get_pago(111).unwrap(), antes);
+      let historial = c.get_historial_categorias(111).unwrap();
+      assert_eq!((historial[0].costo_anterior, historial[0].costo_nuevo), (None, None));
+    }
+    #[ink::test]
+    fn cambiar_categoria_actividades_test(){
+      let mut c=Club::new();
+      c.crear_categoria("D".to_string(), 4000, ActividadesIncluidas::Cantidad(2)).unwrap();
+      c.registrar_socio(111, "D".to_string(), "TENIS".to_string()).unwrap();
+      c.agregar_actividad(111, "FUTBOL".to_string()).unwrap();
+      c.cambiar_categoria(111, "B".to_string()).unwrap();
+      assert_eq!(c.get_info_socio(0), Ok(("B".to_string(), ["TENIS".to_string()].to_vec())));
+      assert_eq!(c.actividades.get(1).unwrap().inscriptos, 0);
+      c.cambiar_categoria(111, "C".to_string()).unwrap();
+      assert_eq!(c.get_info_socio(0), Ok(("C".to_string(), Vec::new())));
+      assert_eq!(c.actividades.get(6).unwrap().inscriptos, 0);
+      assert_eq!(c.get_historial_categorias(111).unwrap().len(), 2);
+    }
+    #[ink::test]
+    fn cambiar_categoria_invalida_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      assert_eq!(c.cambiar_categoria(111, "C".to_string()), Err(ClubError::MismaCategoria));
+      assert_eq!(c.cambiar_categoria(111, "Z".to_string()), Err(ClubError::CategoriaInvalida));
+      assert_eq!(c.cambiar_categoria(222, "A".to_string()), Err(ClubError::SocioNoEncontrado));
+      c.retirar_categoria(1).unwrap();
+      assert_eq!(c.cambiar_categoria(111, "A".to_string()), Err(ClubError::CategoriaInvalida));
+      assert_eq!(c.get_historial_categorias(111), Ok(Vec::new()));
+    }
+    #[ink::test]
+    fn suspender_socio_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      c.suspender_socio(111, "falta de pago".to_string()).unwrap();
+      let socio = c.buscar_socio(111).unwrap();
+      assert_eq!(socio.estado, EstadoSocio::Suspendido);
+      assert_eq!(socio.motivo_estado, Some("falta de pago".to_string()));
+      assert_eq!(c.get_socios(), [(111, EstadoSocio::Suspendido),(222, EstadoSocio::Activo)].to_vec());
+      assert_eq!(c.crear_pagos(111), Err(ClubError::SocioInactivo));
+      assert_eq!(c.agregar_actividad(111, "TENIS".to_string()), Err(ClubError::SocioInactivo));
+      assert_eq!(c.suspender_socio(111, "otra vez".to_string()), Err(ClubError::EstadoInvalido));
+      //la facturacion saltea al socio suspendido
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10 * 24 * 60 * 60);
+      c.facturar_periodo().unwrap();
+      assert_eq!(c.get_pago(111).unwrap().len(), 1);
+      assert_eq!(c.get_pago(222).unwrap().len(), 2);
+    }
+    #[ink::test]
+    fn dar_de_baja_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      c.crear_pagos(111).unwrap();
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+      assert_eq!(c.dar_de_baja(111), Err(ClubError::MontoIncorrecto));
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(6000);
+      c.dar_de_baja(111).unwrap();
+      let socio = c.buscar_socio(111).unwrap();
+      assert_eq!(socio.estado, EstadoSocio::DadoDeBaja);
+      assert!(socio.pagos_pendientes.is_empty());
+      assert!(socio.actividades.is_empty());
+      assert_eq!(c.actividades.get(1).unwrap().inscriptos, 0);
+      assert_eq!(c.tesoreria, 6000);
+      assert!(c.get_pago(111).unwrap().iter().all(|p| p.2));
+      assert_eq!(c.crear_pagos(111), Err(ClubError::SocioInactivo));
+      assert_eq!(c.dar_de_baja(111), Err(ClubError::EstadoInvalido));
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::SocioDadoDeBaja(e)) if e.id_socio == 111 && e.monto == 6000));
+    }
+    #[ink::test]
+    fn dar_de_baja_con_saldo_sin_cuenta_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.crear_pagos(111).unwrap();
+      c.acreditar_saldo(111, 2500, "bonificacion".to_string()).unwrap();
+      //debe 4000, el saldo cubre 2500 y transfiere 500 de mas
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+      c.dar_de_baja(111).unwrap();
+      //el pago cubierto con el saldo queda sin pagador, y quien llama solo es pagador del que cubrio con lo transferido
+      assert_eq!(c.pagos.get(1).unwrap().pagador, None);
+      assert_eq!(c.pagos.get(2).unwrap().pagador, Some([0x01; 32].into()));
+      //sin cuenta vinculada el excedente le queda acreditado al socio, no a quien llama
+      assert_eq!(c.get_saldo_a_favor(111), Ok(500));
+      assert_eq!(c.reembolsos_pendientes.get(AccountId::from([0x01; 32])), None);
+      assert_eq!(c.tesoreria, 2000);
+      let eventos = eventos_emitidos();
+      let n = eventos.len();
+      assert!(matches!(&eventos[n - 2], Event::SaldoAcreditado(e) if e.id_socio == 111 && e.monto == 500 && e.motivo == "excedente de la baja"));
+    }
+    #[ink::test]
+    fn dar_de_baja_devuelve_a_la_cuenta_del_socio_test(){
+      let contrato: AccountId = [0xFF; 32].into();
+      let cuenta: AccountId = [0x02; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 0);
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.crear_pagos(111).unwrap();
+      vincular(&mut c, 111, cuenta);
+      //paga 4500 por el primer pago y le quedan 2500 a favor
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(4500);
+      c.registro_pago(111).unwrap();
+      let antes = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(cuenta).unwrap();
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(300);
+      c.dar_de_baja(111).unwrap();
+      //el saldo cubre el segundo pago, y el saldo que sobra y lo transferido de mas van a la cuenta del socio
+      assert_eq!(c.pagos.get(2).unwrap().pagador, None);
+      assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(cuenta), Ok(antes + 800));
+      assert_eq!(c.get_saldo_a_favor(111), Ok(0));
+      assert_eq!(c.tesoreria, 4000);
+      let eventos = eventos_emitidos();
+      let n = eventos.len();
+      assert!(matches!(&eventos[n - 2], Event::SaldoDevuelto(e) if e.id_socio == 111 && e.cuenta == cuenta && e.monto == 800));
+      //un saldo que la tesoreria no cubre, como una bonificacion, le queda acreditado
+      let otra: AccountId = [0x03; 32].into();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      vincular(&mut c, 222, otra);
+      c.acreditar_saldo(222, 6500, "bonificacion".to_string()).unwrap();
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+      c.dar_de_baja(222).unwrap();
+      assert_eq!(c.get_saldo_a_favor(222), Ok(4500));
+      assert_eq!(c.tesoreria, 4000);
+    }
+    #[ink::test]
+    fn reincorporar_socio_test(){
+      let mut c=Club::new();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      assert_eq!(c.reincorporar_socio(111), Err(ClubError::EstadoInvalido));
+      c.suspender_socio(111, "sancion".to_string()).unwrap();
+      //todavia no vencio su pago, asi que no se le crea otro
+      c.reincorporar_socio(111).unwrap();
+      assert_eq!(c.buscar_socio(111).unwrap().estado, EstadoSocio::Activo);
+      assert_eq!(c.get_pago(111).unwrap().len(), 1);
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+      c.dar_de_baja(111).unwrap();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100 * 24 * 60 * 60);
+      c.reincorporar_socio(111).unwrap();
+      //vuelve con un pago nuevo que vence 10 dias despues de reincorporarse
+      assert_eq!(c.get_pago(111).unwrap()[1], (2, 110 * 24 * 60 * 60, false, 2000, 0));
+      assert_eq!(c.buscar_socio(111).unwrap().pagos_pendientes, [2].to_vec());
+      //el evento se emite despues de crear el pago nuevo
+      let eventos = eventos_emitidos();
+      let n = eventos.len();
+      assert!(matches!(&eventos[n - 2], Event::PagoCreado(e) if e.id == 2));
+      assert!(matches!(&eventos[n - 1], Event::SocioReincorporado(e) if e.id_socio == 111));
+    }
+    #[ink::test]
+    fn racha_pagos_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.set_cantidad_meses(2).unwrap();
+      assert_eq!(c.get_racha_pagos(543), Ok((0, 2)));
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(c.get_racha_pagos(543), Ok((1, 1)));
+      //con un solo pago a tiempo todavia no le corresponde el descuento
+      c.crear_pagos(543).unwrap();
+      assert!(!c.pagos.get(2).unwrap().tiene_descuento);
+      pagar(&mut c, 543, 2000).unwrap();
+      c.crear_pagos(543).unwrap();
+      assert!(c.pagos.get(3).unwrap().tiene_descuento);
+      assert_eq!(c.get_racha_pagos(543), Ok((2, 2)));
+      //el pago 3 vence a los 70 dias, si lo paga despues la racha se corta
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(80 * 24 * 60 * 60);
+      pagar(&mut c, 543, 1400).unwrap();
+      assert_eq!(c.get_racha_pagos(543), Ok((0, 2)));
+      assert_eq!(c.get_racha_pagos(999), Err(ClubError::SocioNoEncontrado));
+    }
+    #[ink::test]
+    fn descuento_no_depende_de_pagos_sin_pagar_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.set_cantidad_meses(1).unwrap();
+      //los pagos creados que todavia no se pagaron no cuentan para la racha
+      c.crear_pagos(543).unwrap();
+      assert!(!c.pagos.get(2).unwrap().tiene_descuento);
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_sin_atrasos, 0);
+    }
+    #[ink::test]
+    fn recargo_por_atraso_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.set_recargos(500, 5, 10).unwrap();
+      //el pago vence a los 10 dias, a los 13 tiene 3 dias de atraso y todavia no corre el recargo fijo
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(13 * 24 * 60 * 60);
+      assert_eq!(c.get_monto_a_pagar(543), Ok((2000, 60)));
+      //a los 16 dias ya se cobra el recargo fijo
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(16 * 24 * 60 * 60);
+      assert_eq!(c.get_monto_a_pagar(543), Ok((2000, 620)));
+      assert_eq!(pagar(&mut c, 543, 0), Err(ClubError::MontoIncorrecto));
+      pagar(&mut c, 543, 2620).unwrap();
+      assert_eq!(c.get_pago(543), Ok([(1, 10 * 24 * 60 * 60, true, 2000, 620)].to_vec()));
+      assert_eq!(c.tesoreria, 2620);
+      assert_eq!(c.get_monto_a_pagar(543), Err(ClubError::SinPagosPendientes));
+    }
+    #[ink::test]
+    fn sin_recargo_antes_del_vencimiento_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.set_recargos(500, 0, 10).unwrap();
+      assert_eq!(c.get_monto_a_pagar(543), Ok((2000, 0)));
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(c.get_pago(543).unwrap()[0].4, 0);
+      let eventos = eventos_emitidos();
+      assert!(eventos.iter().any(|e| matches!(e, Event::RecargosCambiados(r) if r.recargo_fijo == 500 && r.interes_diario == 10)));
+    }
+    #[ink::test]
+    fn pago_parcial_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      pagar(&mut c, 543, 500).unwrap();
+      let pago = c.pagos.get(1).unwrap();
+      assert!(!pago.pagado);
+      assert_eq!(pago.monto_pagado, 500);
+      assert_eq!(c.get_monto_a_pagar(543), Ok((1500, 0)));
+      assert_eq!(c.tesoreria, 500);
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::PagoParcialRegistrado(e)) if e.monto == 500 && e.restante == 1500));
+      //con lo que falta se salda, y lo que sobra queda como saldo a favor
+      pagar(&mut c, 543, 2000).unwrap();
+      let pago = c.pagos.get(1).unwrap();
+      assert!(pago.pagado);
+      assert_eq!(pago.monto_pagado, 2000);
+      assert_eq!(c.tesoreria, 2500);
+      assert_eq!(c.get_saldo_a_favor(543), Ok(500));
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_sin_atrasos, 1);
+      assert_eq!(pagar(&mut c, 543, 100), Err(ClubError::SinPagosPendientes));
+    }
+    #[ink::test]
+    fn plan_cuotas_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.crear_pagos(543).unwrap();
+      pagar(&mut c, 543, 1000).unwrap();
+      //debe 1000 del primer pago y 2000 del segundo
+      assert_eq!(c.crear_plan_cuotas(543, 0, 30), Err(ClubError::CuotasInvalidas));
+      let ids = c.crear_plan_cuotas(543, 2, 15).unwrap();
+      assert_eq!(ids, [3, 4].to_vec());
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_pendientes, [3, 4].to_vec());
+      let pagos = c.get_pago(543).unwrap();
+      assert!(pagos[0].2 && pagos[1].2);
+      assert_eq!(pagos[2], (3, 15 * 24 * 60 * 60, false, 1500, 0));
+      assert_eq!(pagos[3], (4, 30 * 24 * 60 * 60, false, 1500, 0));
+      //las cuotas no corren el calendario de los pagos mensuales
+      c.crear_pagos(543).unwrap();
+      assert_eq!(c.get_pago(543).unwrap()[4].1, 70 * 24 * 60 * 60);
+      pagar(&mut c, 543, 1500).unwrap();
+      assert!(c.pagos.get(3).unwrap().pagado);
+      assert_eq!(c.crear_plan_cuotas(999, 2, 15), Err(ClubError::SocioNoEncontrado));
+    }
+    #[ink::test]
+    fn plan_cuotas_mas_cuotas_que_deuda_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      pagar(&mut c, 543, 1997).unwrap();
+      //debe 3, asi que con 4 cuotas alguna costaria 0
+      assert_eq!(c.crear_plan_cuotas(543, 4, 30), Err(ClubError::CuotasInvalidas));
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_pendientes, [1].to_vec());
+      assert!(!c.pagos.get(1).unwrap().refinanciado);
+      let ids = c.crear_plan_cuotas(543, 3, 30).unwrap();
+      assert!(ids.iter().all(|id| c.pagos.get(id).unwrap().costo == 1));
+    }
+    #[ink::test]
+    fn plan_cuotas_sin_deuda_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(c.crear_plan_cuotas(543, 3, 30), Err(ClubError::SinPagosPendientes));
+    }
+    #[ink::test]
+    fn saldo_a_favor_se_aplica_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      pagar(&mut c, 543, 2500).unwrap();
+      assert_eq!(c.get_saldo_a_favor(543), Ok(500));
+      c.crear_pagos(543).unwrap();
+      let pago = c.pagos.get(2).unwrap();
+      assert!(!pago.pagado);
+      assert_eq!(pago.monto_pagado, 500);
+      assert_eq!(c.get_saldo_a_favor(543), Ok(0));
+      assert_eq!(c.get_monto_a_pagar(543), Ok((1500, 0)));
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::SaldoAplicado(e)) if e.id == 2 && e.monto == 500));
+      //la tesoreria no cambia al usar el saldo, ya habia entrado
+      assert_eq!(c.tesoreria, 2500);
+    }
+    #[ink::test]
+    fn acreditar_saldo_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      assert_eq!(c.acreditar_saldo(999, 100, "x".to_string()), Err(ClubError::SocioNoEncontrado));
+      assert_eq!(c.acreditar_saldo(543, 0, "x".to_string()), Err(ClubError::MontoIncorrecto));
+      c.acreditar_saldo(543, 5000, "bonificacion".to_string()).unwrap();
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::SaldoAcreditado(e)) if e.monto == 5000 && e.motivo == "bonificacion"));
+      //el saldo cubre el pago siguiente entero
+      c.crear_pagos(543).unwrap();
+      assert!(c.pagos.get(2).unwrap().pagado);
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_pendientes, [1].to_vec());
+      assert_eq!(c.get_saldo_a_favor(543), Ok(3000));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x02; 32].into());
+      assert_eq!(c.acreditar_saldo(543, 100, "x".to_string()), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn revertir_pago_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.crear_pagos(543).unwrap();
+      assert_eq!(c.revertir_pago(1, "error".to_string(), false), Err(ClubError::PagoNoPagado));
+      assert_eq!(c.revertir_pago(9, "error".to_string(), false), Err(ClubError::PagoNoEncontrado));
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_pendientes, [2].to_vec());
+      let nota = c.revertir_pago(1, "socio equivocado".to_string(), false).unwrap();
+      assert_eq!(nota, 1);
+      let socio = c.buscar_socio(543).unwrap();
+      assert_eq!(socio.pagos_pendientes, [1, 2].to_vec());
+      assert!(socio.pagos_realizados.is_empty());
+      assert_eq!(socio.pagos_sin_atrasos, 0);
+      let pago = c.pagos.get(1).unwrap();
+      assert!(!pago.pagado);
+      assert_eq!(pago.monto_pagado, 0);
+      //sin reembolso lo pagado queda en la tesoreria y vuelve al socio como saldo a favor
+      assert_eq!(c.tesoreria, 2000);
+      assert_eq!(c.get_saldo_a_favor(543), Ok(2000));
+      let notas = c.get_notas_credito(1).unwrap();
+      assert_eq!(notas.len(), 1);
+      assert_eq!((notas[0].id_pago, notas[0].id_socio, notas[0].monto, notas[0].reembolsado), (1, 543, 2000, false));
+      assert_eq!(notas[0].motivo, "socio equivocado");
+      let eventos = eventos_emitidos();
+      assert!(eventos.iter().any(|e| matches!(e, Event::PagoRevertido(e) if e.id_pago == 1 && e.nota == 1)));
+      assert!(matches!(eventos.last(), Some(Event::SaldoAcreditado(e)) if e.id_socio == 543 && e.monto == 2000 && e.motivo == "socio equivocado"));
+    }
+    #[ink::test]
+    fn revertir_pago_con_saldo_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      c.acreditar_saldo(543, 2000, "bonificacion".to_string()).unwrap();
+      //el pago 2 se paga entero con el saldo a favor, sin pagador
+      c.crear_pagos(543).unwrap();
+      assert_eq!(c.pagos.get(2).unwrap().pagador, None);
+      assert_eq!(c.get_saldo_a_favor(543), Ok(0));
+      //al revertirlo el saldo vuelve al socio aunque se pida reembolsar
+      c.revertir_pago(2, "bonificacion mal cargada".to_string(), true).unwrap();
+      assert_eq!(c.get_saldo_a_favor(543), Ok(2000));
+      assert_eq!(c.tesoreria, 2000);
+      assert!(!c.get_notas_credito(2).unwrap()[0].reembolsado);
+      assert_eq!(c.buscar_socio(543).unwrap().pagos_pendientes, [2].to_vec());
+    }
+    #[ink::test]
+    fn revertir_pago_con_reembolso_test(){
+      let contrato: AccountId = [0xFF; 32].into();
+      let caller: AccountId = [0x01; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 0);
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(2000);
+      c.registro_pago(543).unwrap();
+      assert_eq!(c.pagos.get(1).unwrap().pagador, Some(caller));
+      c.revertir_pago(1, "cobro duplicado".to_string(), true).unwrap();
+      assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contrato), Ok(0));
+      assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(caller), Ok(1_000_000));
+      assert_eq!(c.tesoreria, 0);
+      assert!(c.get_notas_credito(1).unwrap()[0].reembolsado);
+      //solo el owner puede reembolsar
+      c.asignar_rol([0x02; 32].into(), Rol::Administrador).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x02; 32].into());
+      assert_eq!(c.revertir_pago(1, "x".to_string(), true), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn revertir_pago_reembolsa_solo_lo_transferido_test(){
+      let contrato: AccountId = [0xFF; 32].into();
+      let owner: AccountId = [0x01; 32].into();
+      let cajero: AccountId = [0x02; 32].into();
+      ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contrato);
+      ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contrato, 0);
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.asignar_rol(cajero, Rol::Cajero).unwrap();
+      //el pago 2 se cubre con 500 de saldo, 700 del cajero y 800 del owner, que lo termina de pagar
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(2500);
+      c.registro_pago(543).unwrap();
+      c.crear_pagos(543).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cajero);
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(700);
+      c.registro_pago(543).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(owner);
+      ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(800);
+      c.registro_pago(543).unwrap();
+      assert_eq!(c.pagos.get(2).unwrap().pagador, Some(owner));
+      let antes = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(owner).unwrap();
+      c.revertir_pago(2, "cobro duplicado".to_string(), true).unwrap();
+      //al owner solo se le devuelve lo que transfirio, y el resto vuelve al socio como saldo a favor
+      assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(owner), Ok(antes + 800));
+      assert_eq!(c.tesoreria, 3200);
+      assert_eq!(c.get_saldo_a_favor(543), Ok(1200));
+      assert_eq!(c.aportes_pago.get(2), None);
+      let nota = &c.get_notas_credito(2).unwrap()[0];
+      assert_eq!((nota.monto, nota.reembolsado), (2000, true));
+      let eventos = eventos_emitidos();
+      assert!(matches!(eventos.last(), Some(Event::SaldoAcreditado(e)) if e.id_socio == 543 && e.monto == 1200 && e.motivo == "cobro duplicado"));
+    }
+    #[ink::test]
+    fn revertir_pago_recalcula_racha_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.set_cantidad_meses(2).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      c.crear_pagos(543).unwrap();
+      //el pago 2 vence a los 40 dias y se paga vencido, la racha se corta
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(50 * 24 * 60 * 60);
+      pagar(&mut c, 543, 5000).unwrap();
+      assert_eq!(c.get_racha_pagos(543), Ok((0, 2)));
+      //al revertirlo la racha vuelve a contar el pago 1 a tiempo
+      c.revertir_pago(2, "pago de otro socio".to_string(), false).unwrap();
+      assert_eq!(c.get_racha_pagos(543), Ok((1, 1)));
+      //con un socio dado de baja no se puede revertir
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+      c.dar_de_baja(543).unwrap();
+      assert_eq!(c.revertir_pago(1, "error".to_string(), false), Err(ClubError::SocioInactivo));
+    }
+    #[ink::test]
+    fn revertir_pago_recalcula_descuento_test(){
+      let mut c=Club::new();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.set_cantidad_meses(2).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      c.crear_pagos(543).unwrap();
+      pagar(&mut c, 543, 2000).unwrap();
+      //el pago 3 se crea con descuento, y el proximo descuento cuenta desde una racha de 2
+      c.crear_pagos(543).unwrap();
+      assert!(c.pagos.get(3).unwrap().tiene_descuento);
+      assert_eq!(c.get_racha_pagos(543), Ok((2, 2)));
+      //sin el pago 2 la racha es 1, y el descuento del pago 3 se dio con esa racha
+      c.revertir_pago(2, "error".to_string(), false).unwrap();
+      let socio = c.buscar_socio(543).unwrap();
+      assert_eq!((socio.pagos_sin_atrasos, socio.racha_descuento), (1, 1));
+      assert_eq!(c.get_racha_pagos(543), Ok((1, 2)));
+    }
+    #[ink::test]
+    fn roles_permisos_test(){
+      let mut c=Club::new();
+      let cajero: AccountId = [0x10; 32].into();
+      let auditor: AccountId = [0x11; 32].into();
+      let admin: AccountId = [0x12; 32].into();
+      c.asignar_rol(cajero, Rol::Cajero).unwrap();
+      c.asignar_rol(auditor, Rol::Auditor).unwrap();
+      c.asignar_rol(admin, Rol::Administrador).unwrap();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      //el cajero solo registra pagos
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cajero);
+      assert_eq!(c.get_monto_a_pagar(543), Ok((2000, 0)));
+      pagar(&mut c, 543, 2000).unwrap();
+      assert_eq!(c.crear_pagos(543), Err(ClubError::NoAutorizado));
+      assert_eq!(c.get_pago(543), Err(ClubError::NoAutorizado));
+      //el auditor solo consulta
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(auditor);
+      assert_eq!(c.get_pago(543).unwrap().len(), 1);
+      assert_eq!(c.get_socios().len(), 1);
+      assert_eq!(pagar(&mut c, 543, 2000), Err(ClubError::NoAutorizado));
+      assert_eq!(c.registrar_socio(111, "C".to_string(), "".to_string()), Err(ClubError::NoAutorizado));
+      assert_eq!(c.set_descuento(90), Err(ClubError::NoAutorizado));
+      assert_eq!(c.descuento, 30);
+      //el administrador puede todo menos lo del owner
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(admin);
+      c.crear_pagos(543).unwrap();
+      c.set_descuento(90).unwrap();
+      assert_eq!(c.descuento, 90);
+      assert_eq!(c.asignar_rol(auditor, Rol::Administrador), Err(ClubError::NoAutorizado));
+      assert_eq!(c.retirar_fondos(1, admin), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn rol_socio_solo_autoservicio_test(){
+      let mut c=Club::new();
+      let cuenta: AccountId = [0x13; 32].into();
+      c.registrar_socio(543, "C".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(111, "C".to_string(), "".to_string()).unwrap();
+      c.asignar_rol(cuenta, Rol::Socio).unwrap();
+      vincular(&mut c, 543, cuenta);
+      //con el rol de socio no se puede pagar ni consultar lo de otros socios
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.get_monto_a_pagar(111), Err(ClubError::NoAutorizado));
+      assert_eq!(pagar(&mut c, 111, 2000), Err(ClubError::NoAutorizado));
+      assert_eq!(c.get_pago(111), Err(ClubError::NoAutorizado));
+      //solo lo propio, por la cuenta vinculada
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+      c.pagar_mi_cuota().unwrap();
+      assert_eq!(c.mi_estado_de_cuenta().unwrap().id_socio, 543);
+      assert!(c.mi_estado_de_cuenta().unwrap().pagos[0].2);
+    }
+    #[ink::test]
+    fn configurar_aprobadores_test(){
+      let mut c = Club::new();
+      let a: AccountId = [0x42; 32].into();
+      let b: AccountId = [0x43; 32].into();
+      assert_eq!(c.configurar_aprobadores([a, b].to_vec(), 0), Err(ClubError::UmbralInvalido));
+      assert_eq!(c.configurar_aprobadores([a, b].to_vec(), 3), Err(ClubError::UmbralInvalido));
+      assert_eq!(c.configurar_aprobadores([].to_vec(), 1), Err(ClubError::UmbralInvalido));
+      assert_eq!(c.configurar_aprobadores([a, a].to_vec(), 1), Err(ClubError::DireccionExistente));
+      c.configurar_aprobadores([a, b].to_vec(), 2).unwrap();
+      assert_eq!(c.get_aprobadores(), ([a, b].to_vec(), 2));
+      assert_eq!(c.configurar_aprobadores([a].to_vec(), 1), Err(ClubError::RequiereAprobacion));
+      //los cambios sensibles ya no se pueden hacer directamente, ni siquiera el owner
+      assert_eq!(c.set_descuento(50), Err(ClubError::RequiereAprobacion));
+      assert_eq!(c.set_cantidad_meses(5), Err(ClubError::RequiereAprobacion));
+      assert_eq!(c.descuento, 30);
+      assert_eq!(c.cantidad_meses, 3);
+      assert_eq!(c.asignar_rol(a, Rol::Cajero), Err(ClubError::RequiereAprobacion));
+      assert_eq!(c.quitar_rol(a), Err(ClubError::RequiereAprobacion));
+      assert_eq!(c.editar_categoria(1, "A".to_string(), 7000, ActividadesIncluidas::Todas), Err(ClubError::RequiereAprobacion));
+      //el resto de la categoria se puede editar si no cambia el precio
+      c.editar_categoria(1, "AA".to_string(), 5000, ActividadesIncluidas::Todas).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(a);
+      assert_eq!(c.configurar_aprobadores([a].to_vec(), 1), Err(ClubError::NoAutorizado));
+    }
+    #[ink::test]
+    fn proponer_y_aprobar_cambio_test(){
+      let mut c = Club::new();
+      let a: AccountId = [0x42; 32].into();
+      let b: AccountId = [0x43; 32].into();
+      let d: AccountId = [0x44; 32].into();
+      c.configurar_aprobadores([a, b, d].to_vec(), 2).unwrap();
+      assert_eq!(c.proponer_cambio(CambioConfiguracion::Descuento(50)), Err(ClubError::NoAutorizado));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(a);
+      assert_eq!(c.proponer_cambio(CambioConfiguracion::PrecioCategoria(9, 100)), Err(ClubError::CategoriaInvalida));
+      assert_eq!(c.proponer_cambio(CambioConfiguracion::AsignarRol(d, Rol::Owner)), Err(ClubError::RolInvalido));
+      let id = c.proponer_cambio(CambioConfiguracion::Descuento(50)).unwrap();
+      //con una sola aprobacion no se ejecuta
+      assert_eq!(c.descuento, 30);
+      assert_eq!(c.aprobar_cambio(id), Err(ClubError::YaAprobada));
+      assert_eq!(c.aprobar_cambio(7), Err(ClubError::PropuestaNoEncontrada));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(b);
+      c.aprobar_cambio(id).unwrap();
+      assert_eq!(c.descuento, 50);
+      assert!(c.get_propuesta(id).unwrap().ejecutada);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(d);
+      assert_eq!(c.aprobar_cambio(id), Err(ClubError::PropuestaEjecutada));
+      let eventos = eventos_emitidos();
+      let n = eventos.len();
+      assert!(matches!(&eventos[n - 4], Event::CambioPropuesto(e) if e.id == id && e.proponente == a));
+      assert!(matches!(&eventos[n - 3], Event::DescuentoCambiado(e) if e.descuento == 50));
+      assert!(matches!(&eventos[n - 2], Event::CambioAprobado(e) if e.id == id && e.aprobador == b && e.aprobaciones == 2));
+      assert!(matches!(&eventos[n - 1], Event::CambioEjecutado(e) if e.id == id && e.caller == b));
+    }
+    #[ink::test]
+    fn cambios_aprobados_test(){
+      let mut c = Club::new();
+      let a: AccountId = [0x42; 32].into();
+      let b: AccountId = [0x43; 32].into();
+      let cajero: AccountId = [0x45; 32].into();
+      c.configurar_aprobadores([a, b].to_vec(), 2).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(a);
+      let precio = c.proponer_cambio(CambioConfiguracion::PrecioCategoria(1, 7000)).unwrap();
+      let rol = c.proponer_cambio(CambioConfiguracion::AsignarRol(cajero, Rol::Cajero)).unwrap();
+      let meses = c.proponer_cambio(CambioConfiguracion::CantidadMeses(6)).unwrap();
+      let aprobadores = c.proponer_cambio(CambioConfiguracion::Aprobadores([a].to_vec(), 1)).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(b);
+      c.aprobar_cambio(precio).unwrap();
+      c.aprobar_cambio(rol).unwrap();
+      c.aprobar_cambio(aprobadores).unwrap();
+      assert_eq!(c.categorias.get(1).unwrap().precio, 7000);
+      assert_eq!(c.get_rol(cajero), Some(Rol::Cajero));
+      assert_eq!(c.get_aprobadores(), ([a].to_vec(), 1));
+      //b ya no es aprobador
+      assert_eq!(c.aprobar_cambio(meses), Err(ClubError::NoAutorizado));
+      //con un solo aprobador y umbral 1, la propuesta se ejecuta al proponerla
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(a);
+      c.proponer_cambio(CambioConfiguracion::QuitarRol(cajero)).unwrap();
+      assert_eq!(c.get_rol(cajero), None);
+      //se desactiva la aprobacion multiple y se vuelve a poder cambiar directamente
+      c.proponer_cambio(CambioConfiguracion::Aprobadores([].to_vec(), 0)).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x01; 32].into());
+      c.set_descuento(10).unwrap();
+      assert_eq!(c.descuento, 10);
+    }
+    #[ink::test]
+    fn propuesta_cambio_vencida_test(){
+      let mut c = Club::new();
+      let a: AccountId = [0x42; 32].into();
+      let b: AccountId = [0x43; 32].into();
+      c.configurar_aprobadores([a, b].to_vec(), 2).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(a);
+      let id = c.proponer_cambio(CambioConfiguracion::Descuento(50)).unwrap();
+      assert_eq!(c.get_propuesta(id).unwrap().vencimiento, 7 * 24 * 60 * 60);
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(8 * 24 * 60 * 60);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(b);
+      assert_eq!(c.aprobar_cambio(id), Err(ClubError::PropuestaVencida));
+      assert_eq!(c.descuento, 30);
+    }
+    #[ink::test]
+    fn programar_cambio_test(){
+      let mut c = Club::new();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+      assert_eq!(c.programar_cambio(ValorProgramado::PrecioCategoria(9, 6000), 2000), Err(ClubError::CategoriaInvalida));
+      assert_eq!(c.programar_cambio(ValorProgramado::PrecioCategoria(1, 6000), 1000), Err(ClubError::FechaInvalida));
+      let id = c.programar_cambio(ValorProgramado::PrecioCategoria(1, 6000), 2000).unwrap();
+      c.programar_cambio(ValorProgramado::Descuento(50), 3000).unwrap();
+      assert_eq!(c.get_cambios_programados().len(), 2);
+      //hasta la fecha sigue rigiendo el precio actual
+      assert_eq!(c.calcular_precio(1), Ok(5000));
+      assert_eq!(c.descuento_vigente(), 30);
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+      assert_eq!(c.calcular_precio(1), Ok(6000));
+      assert_eq!(c.descuento_vigente(), 30);
+      //todavia no se aplico al catalogo, pero ya rige
+      assert_eq!(c.categorias.get(1).unwrap().precio, 5000);
+      assert_eq!(c.get_cambios_programados()[0].id, id);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x42; 32].into());
+      assert_eq!(c.programar_cambio(ValorProgramado::Descuento(10), 5000), Err(ClubError::NoAutorizado));
+      assert_eq!(c.get_cambios_programados(), Vec::new());
+    }
+    #[ink::test]
+    fn aplicar_cambios_programados_test(){
+      let mut c = Club::new();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      let mes: u64 = 30 * 24 * 60 * 60;
+      c.programar_cambio(ValorProgramado::PrecioCategoria(1, 6000), mes).unwrap();
+      c.programar_cambio(ValorProgramado::PrecioCategoria(1, 6500), mes + 10).unwrap();
+      c.programar_cambio(ValorProgramado::PrecioCategoria(2, 4000), 3 * mes).unwrap();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(mes + 10);
+      c.crear_pagos(44851840).unwrap();
+      assert_eq!(c.pagos.get(2).unwrap().costo, 6500);
+      assert_eq!(c.categorias.get(1).unwrap().precio, 6500);
+      assert_eq!(c.get_cambios_programados().len(), 1);
+      assert_eq!(c.get_historial_precios(1), Ok([
+        CambioPrecio{ anterior: 5000, nuevo: 6000, fecha: mes },
+        CambioPrecio{ anterior: 6000, nuevo: 6500, fecha: mes + 10 },
+      ].to_vec()));
+      assert_eq!(c.get_historial_precios(2), Ok(Vec::new()));
+      assert_eq!(c.get_historial_precios(9), Err(ClubError::CategoriaInvalida));
+      //los cambios inmediatos tambien quedan en el historial
+      c.editar_categoria(2, "B".to_string(), 3500, ActividadesIncluidas::Cantidad(1)).unwrap();
+      assert_eq!(c.get_historial_precios(2).unwrap()[0], CambioPrecio{ anterior: 3000, nuevo: 3500, fecha: mes + 10 });
+    }
+    #[ink::test]
+    fn cancelar_cambio_programado_test(){
+      let mut c = Club::new();
+      let id = c.programar_cambio(ValorProgramado::Descuento(50), 1000).unwrap();
+      assert_eq!(c.cancelar_cambio_programado(7), Err(ClubError::CambioNoEncontrado));
+      c.cancelar_cambio_programado(id).unwrap();
+      assert_eq!(c.cancelar_cambio_programado(id), Err(ClubError::CambioNoEncontrado));
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+      assert_eq!(c.descuento_vigente(), 30);
+      //un cambio que ya rige se aplica y no se puede cancelar
+      let id = c.programar_cambio(ValorProgramado::Descuento(40), 2000).unwrap();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+      assert_eq!(c.cancelar_cambio_programado(id), Err(ClubError::CambioNoEncontrado));
+      assert_eq!(c.descuento, 40);
+      let eventos = eventos_emitidos();
+      assert!(matches!(&eventos[0], Event::CambioProgramadoCreado(e) if e.id == 1 && e.fecha_vigencia == 1000));
+      assert!(matches!(&eventos[1], Event::CambioProgramadoCancelado(e) if e.id == 1));
+      assert!(matches!(&eventos[3], Event::DescuentoCambiado(e) if e.descuento == 40));
+    }
+    #[ink::test]
+    fn programar_cambio_con_aprobacion_test(){
+      let mut c = Club::new();
+      let a: AccountId = [0x42; 32].into();
+      let b: AccountId = [0x43; 32].into();
+      c.configurar_aprobadores([a, b].to_vec(), 2).unwrap();
+      assert_eq!(c.programar_cambio(ValorProgramado::Descuento(50), 1000), Err(ClubError::RequiereAprobacion));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(a);
+      assert_eq!(c.proponer_cambio(CambioConfiguracion::Programado(ValorProgramado::Descuento(50), 0)), Err(ClubError::FechaInvalida));
+      let id = c.proponer_cambio(CambioConfiguracion::Programado(ValorProgramado::PrecioCategoria(1, 6000), 1000)).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(b);
+      c.aprobar_cambio(id).unwrap();
+      assert_eq!(c.cambios_programados.len(), 1);
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+      assert_eq!(c.calcular_precio(1), Ok(6000));
+    }
+    #[ink::test]
+    fn pausar_test(){
+      let mut c = Club::new();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      assert_eq!(c.reanudar(), Err(ClubError::ContratoNoPausado));
+      c.pausar("Error en registro_pago".to_string(), None).unwrap();
+      let pausa = c.get_pausa().unwrap();
+      assert_eq!(pausa.motivo, "Error en registro_pago".to_string());
+      assert_eq!(pausa.hasta, None);
+      //las consultas siguen funcionando
+      assert_eq!(c.get_socios(), [(44851840, EstadoSocio::Activo)].to_vec());
+      assert_eq!(c.get_pago(44851840).unwrap().len(), 1);
+      c.reanudar().unwrap();
+      assert_eq!(c.get_pausa(), None);
+      c.crear_pagos(44851840).unwrap();
+      let eventos = eventos_emitidos();
+      let n = eventos.len();
+      assert!(matches!(&eventos[n - 3], Event::ContratoPausado(e) if e.motivo == "Error en registro_pago" && e.hasta.is_none()));
+      assert!(matches!(&eventos[n - 2], Event::ContratoReanudado(_)));
+      assert!(matches!(&eventos[n - 1], Event::PagoCreado(_)));
+    }
+    #[ink::test]
+    fn pausar_sin_permiso_test(){
+      let mut c = Club::new();
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+      assert_eq!(c.pausar("motivo".to_string(), Some(1000)), Err(ClubError::FechaInvalida));
+      c.asignar_rol([0x42; 32].into(), Rol::Administrador).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x42; 32].into());
+      assert_eq!(c.pausar("motivo".to_string(), None), Err(ClubError::NoAutorizado));
+      assert_eq!(c.reanudar(), Err(ClubError::NoAutorizado));
+      assert_eq!(c.get_pausa(), None);
+    }
+    #[ink::test]
+    fn pausa_se_reanuda_sola_test(){
+      let mut c = Club::new();
+      c.pausar("Mantenimiento".to_string(), Some(1000)).unwrap();
+      assert_eq!(c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()), Err(ClubError::ContratoEnPausa));
+      ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+      assert_eq!(c.get_pausa(), None);
+      assert_eq!(c.reanudar(), Err(ClubError::ContratoNoPausado));
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+    }
+    #[ink::test]
+    fn pausa_bloquea_operaciones_test(){
+      let mut c = Club::new();
+      let otro: AccountId = [0x42; 32].into();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(44851842, "C".to_string(), "".to_string()).unwrap();
+      c.crear_grupo(44851840, 10).unwrap();
+      c.pausar("Emergencia".to_string(), None).unwrap();
+      let p = Err(ClubError::ContratoEnPausa);
+      assert_eq!(c.asignar_rol(otro, Rol::Cajero), p);
+      assert_eq!(c.quitar_rol(otro), p);
+      assert_eq!(c.configurar_aprobadores([otro].to_vec(), 1), p);
+      assert_eq!(c.proponer_cambio(CambioConfiguracion::Descuento(10)), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.aprobar_cambio(1), p);
+      assert_eq!(c.registrar_socio(44851841, "B".to_string(), "FUTBOL".to_string()), p);
+      assert_eq!(pagar(&mut c, 44851840, 5000), p);
+      assert_eq!(c.reclamar_reembolso(), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.pagar_mi_cuota(), p);
+      assert_eq!(c.solicitar_vinculo(44851840), p);
+      assert_eq!(c.aprobar_vinculo(otro), p);
+      assert_eq!(c.desvincular_cuenta(44851840), p);
+      assert_eq!(c.acreditar_saldo(44851840, 100, "motivo".to_string()), p);
+      assert_eq!(c.revertir_pago(1, "motivo".to_string(), false), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.retirar_fondos(100, otro), p);
+      assert_eq!(c.crear_plan_cuotas(44851840, 2, 10), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.crear_pagos(44851840), p);
+      assert_eq!(c.facturar_periodo(), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.crear_categoria("D".to_string(), 1000, ActividadesIncluidas::Todas), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.editar_categoria(1, "A".to_string(), 1, ActividadesIncluidas::Todas), p);
+      assert_eq!(c.retirar_categoria(1), p);
+      assert_eq!(c.programar_cambio(ValorProgramado::Descuento(10), 1000), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.cancelar_cambio_programado(1), p);
+      assert_eq!(c.agregar_actividad(44851840, "TENIS".to_string()), p);
+      assert_eq!(c.quitar_actividad(44851840, "FUTBOL".to_string()), p);
+      assert_eq!(c.cambiar_categoria(44851840, "B".to_string()), p);
+      assert_eq!(c.suspender_socio(44851840, "motivo".to_string()), p);
+      assert_eq!(c.dar_de_baja(44851840), p);
+      assert_eq!(c.reincorporar_socio(44851840), p);
+      assert_eq!(c.crear_actividad("VOLEY".to_string(), None, None), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.editar_actividad(1, "FUTBOL".to_string(), Some(1), None), p);
+      assert_eq!(c.retirar_actividad(1), p);
+      assert_eq!(c.migrar_datos(Vec::new(), Vec::new()), p);
+      assert_eq!(c.crear_grupo(44851842, 10), Err(ClubError::ContratoEnPausa));
+      assert_eq!(c.agregar_integrante(1, 44851842), p);
+      assert_eq!(c.quitar_integrante(1, 44851842), p);
+      assert_eq!(c.set_descuento_grupo(1, 20), p);
+      assert_eq!(c.disolver_grupo(1), p);
+      assert_eq!(c.pagar_grupo(1), p);
+      assert_eq!(c.pagar_mi_grupo(), p);
+      assert_eq!(c.get_grupo(1).unwrap().descuento, 10);
+      assert_eq!(c.set_descuento(50), p);
+      assert_eq!(c.set_cantidad_meses(5), p);
+      assert_eq!(c.set_recargos(500, 5, 2), p);
+      assert_eq!(c.set_limite_facturacion(10), p);
+      assert_eq!((c.descuento, c.cantidad_meses, c.recargo_fijo, c.limite_facturacion), (30, 3, 0, 50));
+      //el cambio de owner sigue funcionando, para poder cambiar una clave comprometida
+      transferir_owner(&mut c, otro);
+      assert_eq!(c.get_rol(otro), Some(Rol::Owner));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(otro);
+      assert_eq!(c.get_socios(), [(44851840, EstadoSocio::Activo), (44851842, EstadoSocio::Activo)].to_vec());
+    }
+    ///La raiz del contrato con los campos de la primera version que se podia actualizar, en el mismo orden
+    ///Sirve para escribir los bytes que leeria el codigo nuevo despues de actualizar un contrato viejo
+    #[ink::storage_item]
+    #[derive(Default)]
+    struct RaizVieja{
+      socios: Mapping<u128, Socio>,
+      orden_socios: Mapping<u32, u128>,
+      cantidad_socios: u32,
+      pagos: Mapping<u128, Pago>,
+      cantidad_pagos: u128,
+      pagos_socio: Mapping<u128, Vec<u128>>,
+      categorias: Mapping<u32, Categoria>,
+      cantidad_categorias: u32,
+      cambios_programados: Vec<CambioProgramado>,
+      cantidad_cambios_programados: u32,
+      historial_precios: Mapping<u32, Vec<CambioPrecio>>,
+      owner: Option<AccountId>,
+      owner_propuesto: Option<(AccountId, u64)>,
+      pausa: Option<Pausa>,
+      version_storage: u32,
+      cursor_migracion: u32,
+      roles: Mapping<AccountId, Rol>,
+      cuentas_con_rol: Vec<AccountId>,
+      aprobadores: Vec<AccountId>,
+      umbral_aprobacion: u32,
+      propuestas: Mapping<u32, Propuesta>,
+      cantidad_propuestas: u32,
+      descuento: u128,
+      cantidad_meses: u128,
+      recargo_fijo: u128,
+      dias_recargo: u64,
+      interes_diario: u128,
+      tesoreria: Balance,
+      retiros: Vec<Retiro>,
+      reembolsos_pendientes: Mapping<AccountId, Balance>,
+      total_reembolsos_pendientes: Balance,
+      saldos_a_favor: Mapping<u128, Balance>,
+      notas_credito: Mapping<u128, NotaCredito>,
+      cantidad_notas_credito: u128,
+      notas_pago: Mapping<u128, Vec<u128>>,
+      cursor_facturacion: u32,
+      limite_facturacion: u32,
+      actividades: Mapping<u32, Actividad>,
+      cantidad_actividades: u32,
+      historial_categorias: Mapping<u128, Vec<CambioCategoria>>,
+    }
+    ///Escribe la raiz del club con el formato viejo y la version de storage recibida, y devuelve el club que lee de ahi el codigo actual
+    fn guardar_raiz_vieja(c: &Club, version_storage: u32)->Club{
+      let mut raiz = RaizVieja{ cantidad_socios: c.cantidad_socios, cantidad_pagos: c.cantidad_pagos, cantidad_categorias: c.cantidad_categorias,
+        cambios_programados: c.cambios_programados.clone(), cantidad_cambios_programados: c.cantidad_cambios_programados, owner: c.owner,
+        owner_propuesto: c.owner_propuesto, pausa: c.pausa.clone(), version_storage: c.version_storage, cursor_migracion: c.cursor_migracion,
+        cuentas_con_rol: c.cuentas_con_rol.clone(), aprobadores: c.aprobadores.clone(), umbral_aprobacion: c.umbral_aprobacion,
+        cantidad_propuestas: c.cantidad_propuestas, descuento: c.descuento, cantidad_meses: c.cantidad_meses, recargo_fijo: c.recargo_fijo,
+        dias_recargo: c.dias_recargo, interes_diario: c.interes_diario, tesoreria: c.tesoreria, retiros: c.retiros.clone(),
+        total_reembolsos_pendientes: c.total_reembolsos_pendientes, cantidad_notas_credito: c.cantidad_notas_credito,
+        cursor_facturacion: c.cursor_facturacion, limite_facturacion: c.limite_facturacion, cantidad_actividades: c.cantidad_actividades,
+        ..Default::default() };
+      //con los mismos valores la raiz actual y la vieja tienen que ocupar los mismos bytes
+      let (mut actual, mut vieja) = (Vec::new(), Vec::new());
+      Storable::encode(c, &mut actual);
+      Storable::encode(&raiz, &mut vieja);
+      assert_eq!(actual, vieja);
+      raiz.version_storage = version_storage;
+      raiz.cursor_migracion = 0;
+      ink::env::set_contract_storage(&<Club as StorageKey>::KEY, &raiz);
+      ink::env::get_contract_storage::<_, Club>(&<Club as StorageKey>::KEY).unwrap().unwrap()
+    }
+    ///Guarda los socios y los pagos del club como los guardaba la version 1, junto con la raiz vieja, para simular los datos de antes de actualizar el codigo
+    fn volver_a_version_1(c: &Club)->Club{
+      for pos in 0..c.cantidad_socios{
+        let s = c.socios.get(c.orden_socios.get(pos).unwrap()).unwrap();
+        let viejo = SocioV1{ dni: s.id, categoria: s.categoria, actividades: s.actividades, fecha_registro: s.fecha_registro,
+          pagos_realizados: s.pagos_realizados, pagos_pendientes: s.pagos_pendientes, pagos_sin_atrasos: s.pagos_sin_atrasos };
+        ink::env::set_contract_storage(&(c.socios.key(), s.id), &viejo);
+      }
+      for id in 1..=c.cantidad_pagos{
+        let p = c.pagos.get(id).unwrap();
+        let viejo = PagoV1{ id: p.id, dni_socio: p.id_socio, costo: p.costo, fecha_pago: p.fecha_pago,
+          fecha_vencimiento: p.fecha_vencimiento, pagado: p.pagado, tiene_descuento: p.tiene_descuento };
+        ink::env::set_contract_storage(&(c.pagos.key(), id), &viejo);
+      }
+      guardar_raiz_vieja(c, 1)
+    }
+    #[ink::test]
+    fn migrar_test(){
+      let mut c = Club::new();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(44851841, "C".to_string(), "NADA".to_string()).unwrap();
+      pagar(&mut c, 44851840, 5000).unwrap();
+      assert_eq!(c.get_version(), (3, 3));
+      assert_eq!(c.migrar(), Err(ClubError::SinMigracionPendiente));
+      let mut c = volver_a_version_1(&c);
+      assert_eq!(c.get_version(), (1, 3));
+      assert_eq!((c.cantidad_socios, c.cantidad_pagos, c.tesoreria, c.limite_facturacion), (2, 2, 5000, 50));
+      //hasta que no se migre no se puede modificar nada
+      assert_eq!(c.registrar_socio(44851842, "B".to_string(), "FUTBOL".to_string()), Err(ClubError::MigracionPendiente));
+      assert_eq!(pagar(&mut c, 44851841, 2000), Err(ClubError::MigracionPendiente));
+      assert_eq!(c.set_limite_migracion(0), Err(ClubError::LimiteInvalido));
+      c.set_limite_migracion(3).unwrap();
+      let (id1, id2) = (id_de(44851840), id_de(44851841));
+      c.cargar_ids_migracion([(44851840, id1), (44851841, id2)].to_vec(), Vec::new()).unwrap();
+      //2 pagos y 2 socios, en dos tandas de 3
+      assert_eq!(c.migrar(), Ok(false));
+      assert_eq!(c.migrar(), Ok(true));
+      assert_eq!(c.get_version(), (3, 3));
+      //los datos sobreviven con los campos nuevos completos, guardados por el id de cada socio
+      let socio = c.socios.get(id1).unwrap();
+      assert_eq!(socio.estado, EstadoSocio::Activo);
+      assert_eq!(socio.pagos_realizados, [1].to_vec());
+      let pago = c.pagos.get(1).unwrap();
+      assert!(pago.pagado);
+      assert_eq!(pago.monto_pagado, 5000);
+      assert_eq!(pago.id_socio, id1);
+      assert_eq!(c.pagos.get(2).unwrap().monto_pagado, 0);
+      pagar(&mut c, id2, 2000).unwrap();
+      assert!(c.get_pago(id2).unwrap()[0].2);
+      //los campos que no estaban en la raiz vieja arrancan vacios
+      assert_eq!(c.crear_grupo(id1, 10), Ok(1));
+      let eventos = eventos_emitidos();
+      assert!(eventos.iter().any(|e| matches!(e, Event::StorageMigrado(e) if e.anterior == 1 && e.version == 3)));
+    }
+    #[ink::test]
+    fn migrar_desbordamiento_test(){
+      let mut c = Club::new();
+      c.cantidad_pagos = u32::MAX as u128 + 1;
+      let mut c = guardar_raiz_vieja(&c, 2);
+      assert_eq!(c.migrar(), Err(ClubError::Desbordamiento));
+      assert_eq!(c.get_version(), (2, 3));
+    }
+    #[ink::test]
+    fn actualizar_codigo_sin_permiso_test(){
+      let c = Club::new();
+      let mut c = volver_a_version_1(&c);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x42; 32].into());
+      assert_eq!(c.actualizar_codigo([0x07; 32]), Err(ClubError::NoAutorizado));
+      assert_eq!(c.migrar(), Err(ClubError::NoAutorizado));
+      assert_eq!(c.set_limite_migracion(10), Err(ClubError::NoAutorizado));
+    }
+    ///Vincula la cuenta al socio, pidiendo el vinculo desde la cuenta y aprobandolo desde el owner
+    fn vincular(c: &mut Club, id_socio: u128, cuenta: AccountId){
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      c.solicitar_vinculo(id_socio).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x01; 32].into());
+      c.aprobar_vinculo(cuenta).unwrap();
+    }
+    #[ink::test]
+    fn vincular_cuenta_test(){
+      let mut c = Club::new();
+      let cuenta: AccountId = [0x42; 32].into();
+      let otra: AccountId = [0x43; 32].into();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(44851841, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.solicitar_vinculo(1), Err(ClubError::SocioNoEncontrado));
+      c.solicitar_vinculo(44851840).unwrap();
+      //hasta que no se aprueba no esta vinculada, y la misma cuenta no se puede aprobar
+      assert_eq!(c.mi_estado_de_cuenta(), Err(ClubError::CuentaNoVinculada));
+      assert_eq!(c.aprobar_vinculo(cuenta), Err(ClubError::NoAutorizado));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x01; 32].into());
+      assert_eq!(c.aprobar_vinculo(otra), Err(ClubError::SinVinculoPendiente));
+      c.aprobar_vinculo(cuenta).unwrap();
+      assert_eq!(c.get_cuenta_socio(44851840), Ok(Some(cuenta)));
+      assert_eq!(c.get_cuenta_socio(44851841), Ok(None));
+      assert_eq!(c.aprobar_vinculo(cuenta), Err(ClubError::SinVinculoPendiente));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(otra);
+      assert_eq!(c.solicitar_vinculo(44851840), Err(ClubError::SocioYaVinculado));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.solicitar_vinculo(44851841), Err(ClubError::CuentaYaVinculada));
+      let eventos = eventos_emitidos();
+      let n = eventos.len();
+      assert!(matches!(&eventos[n - 2], Event::VinculoSolicitado(e) if e.id_socio == 44851840 && e.cuenta == cuenta));
+      assert!(matches!(&eventos[n - 1], Event::CuentaVinculada(e) if e.id_socio == 44851840 && e.cuenta == cuenta));
+    }
+    #[ink::test]
+    fn desvincular_cuenta_test(){
+      let mut c = Club::new();
+      let cuenta: AccountId = [0x42; 32].into();
+      let otra: AccountId = [0x43; 32].into();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      assert_eq!(c.desvincular_cuenta(44851840), Err(ClubError::CuentaNoVinculada));
+      vincular(&mut c, 44851840, cuenta);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(otra);
+      assert_eq!(c.desvincular_cuenta(44851840), Err(ClubError::NoAutorizado));
+      //la cuenta vinculada se puede desvincular sola
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      c.desvincular_cuenta(44851840).unwrap();
+      assert_eq!(c.mi_estado_de_cuenta(), Err(ClubError::CuentaNoVinculada));
+      vincular(&mut c, 44851840, otra);
+      c.desvincular_cuenta(44851840).unwrap();
+      assert_eq!(c.get_cuenta_socio(44851840), Ok(None));
+    }
+    #[ink::test]
+    fn pagar_mi_cuota_test(){
+      let mut c = Club::new();
+      let cuenta: AccountId = [0x42; 32].into();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(44851841, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+      assert_eq!(c.pagar_mi_cuota(), Err(ClubError::CuentaNoVinculada));
+      vincular(&mut c, 44851840, cuenta);
+      //sin rol no puede usar registro_pago, pero si pagar su cuota
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.registro_pago(44851841), Err(ClubError::NoAutorizado));
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+      c.pagar_mi_cuota().unwrap();
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(3500);
+      c.pagar_mi_cuota().unwrap();
+      assert_eq!(c.tesoreria, 5500);
+      let pago = c.pagos.get(1).unwrap();
+      assert!(pago.pagado);
+      assert_eq!(pago.pagador, Some(cuenta));
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+      assert_eq!(c.pagar_mi_cuota(), Err(ClubError::SinPagosPendientes));
+    }
+    #[ink::test]
+    fn mi_estado_de_cuenta_test(){
+      let mut c = Club::new();
+      let cuenta: AccountId = [0x42; 32].into();
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(44851841, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      vincular(&mut c, 44851840, cuenta);
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      //sin rol no puede consultar los datos del club
+      assert_eq!(c.get_pago(44851841), Err(ClubError::NoAutorizado));
+      assert_eq!(c.get_socios(), Vec::new());
+      let estado = c.mi_estado_de_cuenta().unwrap();
+      assert_eq!(estado, EstadoCuenta{
+        id_socio: 44851840,
+        categoria: "A".to_string(),
+        estado: EstadoSocio::Activo,
+        pagos: [(1, 10 * 24 * 60 * 60, false, 5000, 0)].to_vec(),
+        saldo_a_favor: 0,
+        monto_a_pagar: Some((5000, 0)),
+      });
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5500);
+      c.pagar_mi_cuota().unwrap();
+      let estado = c.mi_estado_de_cuenta().unwrap();
+      assert_eq!(estado.saldo_a_favor, 500);
+      assert_eq!(estado.monto_a_pagar, None);
+      assert!(estado.pagos[0].2);
+    }
+    ///Sal secreta que usan los tests para calcular los ids de los socios fuera del contrato
+    const SAL: [u8; 32] = [0x07; 32];
+    ///Calcula el id de un socio con la sal de los tests
+    fn id_de(dni: u128)->u128{
+      Club::calcular_id_socio(SAL, dni)
+    }
+    #[ink::test]
+    fn calcular_id_socio_test(){
+      let id = id_de(44851840);
+      assert_ne!(id, 44851840);
+      assert_eq!(id, id_de(44851840));
+      assert_ne!(id, id_de(44851841));
+      //con otra sal el mismo dni da otro id
+      assert_ne!(Club::calcular_id_socio([0x08; 32], 44851840), id);
+    }
+    #[ink::test]
+    fn migrar_ids_test(){
+      let mut c = Club::new();
+      let cuenta: AccountId = [0x42; 32].into();
+      //socios guardados por dni, como en la version 2
+      c.registrar_socio(44851840, "A".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(44851841, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      pagar(&mut c, 44851840, 5500).unwrap();
+      c.revertir_pago(1, "error".to_string(), false).unwrap();
+      c.cambiar_categoria(44851841, "C".to_string()).unwrap();
+      vincular(&mut c, 44851841, cuenta);
+      //un pedido de vinculo que todavia no se aprobo, guardado con el dni
+      let pendiente: AccountId = [0x43; 32].into();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(pendiente);
+      c.solicitar_vinculo(44851840).unwrap();
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x01; 32].into());
+      let mut c = guardar_raiz_vieja(&c, 2);
+      //sin los ids cargados no se puede migrar, y solo el owner los puede cargar
+      assert_eq!(c.migrar(), Err(ClubError::IdNoCargado));
+      let (id1, id2) = (id_de(44851840), id_de(44851841));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.cargar_ids_migracion([(44851840, id1)].to_vec(), Vec::new()), Err(ClubError::NoAutorizado));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>([0x01; 32].into());
+      c.cargar_ids_migracion([(44851840, id1), (44851841, id2)].to_vec(), [pendiente].to_vec()).unwrap();
+      assert_eq!(c.vinculos_pendientes.get(pendiente), Some(id1));
+      //pasar la misma cuenta otra vez no cambia el pedido que ya esta con el id
+      c.cargar_ids_migracion(Vec::new(), [pendiente, cuenta].to_vec()).unwrap();
+      assert_eq!(c.vinculos_pendientes.get(pendiente), Some(id1));
+      assert_eq!(c.migrar(), Ok(true));
+      assert_eq!(c.cargar_ids_migracion(Vec::new(), Vec::new()), Err(ClubError::SinMigracionPendiente));
+      //el pedido pendiente se aprueba contra el socio migrado
+      c.aprobar_vinculo(pendiente).unwrap();
+      assert_eq!(c.get_cuenta_socio(id1), Ok(Some(pendiente)));
+      //los ids cargados se borran al migrar
+      assert!(!c.ids_migracion.contains(44851840));
+      assert!(!c.existe_socio(44851840));
+      assert_eq!(c.get_socios(), [(id1, EstadoSocio::Activo), (id2, EstadoSocio::Activo)].to_vec());
+      assert_eq!(c.pagos.get(1).unwrap().id_socio, id1);
+      assert_eq!(c.get_pago(id1).unwrap().len(), 1);
+      assert_eq!(c.get_saldo_a_favor(id1), Ok(5500));
+      assert_eq!(c.notas_credito.get(1).unwrap().id_socio, id1);
+      assert_eq!(c.get_historial_categorias(id2).unwrap().len(), 1);
+      assert_eq!(c.get_cuenta_socio(id2), Ok(Some(cuenta)));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      assert_eq!(c.mi_estado_de_cuenta().unwrap().id_socio, id2);
+    }
+    #[ink::test]
+    fn grupo_familiar_test(){
+      let mut c = Club::new();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "B".to_string(), "FUTBOL".to_string()).unwrap();
+      c.registrar_socio(333, "C".to_string(), "".to_string()).unwrap();
+      assert_eq!(c.crear_grupo(111, 101), Err(ClubError::DescuentoInvalido));
+      assert_eq!(c.crear_grupo(444, 10), Err(ClubError::SocioNoEncontrado));
+      assert_eq!(c.crear_grupo(111, 10), Ok(1));
+      c.agregar_integrante(1, 222).unwrap();
+      c.agregar_integrante(1, 333).unwrap();
+      assert_eq!(c.agregar_integrante(1, 222), Err(ClubError::SocioEnGrupo));
+      assert_eq!(c.crear_grupo(222, 10), Err(ClubError::SocioEnGrupo));
+      assert_eq!(c.agregar_integrante(2, 444), Err(ClubError::GrupoNoEncontrado));
+      assert_eq!(c.get_grupos(), [(1, [111, 222, 333].to_vec())].to_vec());
+      assert_eq!(c.get_grupo(1).unwrap().titular, 111);
+      //el descuento del grupo se aplica desde el proximo pago de cada integrante
+      c.crear_pagos(222).unwrap();
+      assert_eq!(c.get_pago(222).unwrap()[0].3, 3000);
+      assert_eq!(c.get_pago(222).unwrap()[1].3, 2700);
+      let eventos = eventos_emitidos();
+      assert!(eventos.iter().any(|e| matches!(e, Event::GrupoCreado(e) if e.id == 1 && e.titular == 111 && e.descuento == 10)));
+      assert!(eventos.iter().any(|e| matches!(e, Event::IntegranteAgregado(e) if e.id == 1 && e.id_socio == 333)));
+    }
+    #[ink::test]
+    fn pagar_grupo_test(){
+      let mut c = Club::new();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      c.crear_grupo(111, 0).unwrap();
+      c.agregar_integrante(1, 222).unwrap();
+      assert_eq!(c.get_deuda_grupo(1), Ok([(111, 5000), (222, 2000)].to_vec()));
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+      assert_eq!(c.pagar_grupo(1), Err(ClubError::MontoIncorrecto));
+      //se paga primero lo del titular y lo que sobra va al integrante, como pago parcial
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(6000);
+      c.pagar_grupo(1).unwrap();
+      assert!(c.pagos.get(1).unwrap().pagado);
+      assert_eq!(c.pagos.get(2).unwrap().monto_pagado, 1000);
+      assert_eq!(c.get_deuda_grupo(1), Ok([(111, 0), (222, 1000)].to_vec()));
+      //el excedente queda a favor del titular
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1500);
+      c.pagar_grupo(1).unwrap();
+      assert!(c.pagos.get(2).unwrap().pagado);
+      assert_eq!(c.get_saldo_a_favor(111), Ok(500));
+      assert_eq!(c.get_saldo_a_favor(222), Ok(0));
+      assert_eq!(c.tesoreria, 7500);
+      assert!(matches!(eventos_emitidos().last(), Some(Event::PagoGrupoRegistrado(e)) if e.id == 1 && e.monto == 1500));
+      assert_eq!(c.pagar_grupo(1), Err(ClubError::SinPagosPendientes));
+      assert_eq!(c.pagar_grupo(2), Err(ClubError::GrupoNoEncontrado));
+    }
+    #[ink::test]
+    fn pagar_mi_grupo_test(){
+      let mut c = Club::new();
+      let cuenta: AccountId = [0x42; 32].into();
+      let otra: AccountId = [0x43; 32].into();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      c.crear_grupo(111, 0).unwrap();
+      c.agregar_integrante(1, 222).unwrap();
+      vincular(&mut c, 111, cuenta);
+      vincular(&mut c, 222, otra);
+      //el integrante a cargo no puede pagar por el grupo
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(otra);
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(7000);
+      assert_eq!(c.pagar_mi_grupo(), Err(ClubError::NoEsTitular));
+      assert_eq!(c.pagar_grupo(1), Err(ClubError::NoAutorizado));
+      ink::env::test::set_caller::<ink::env::DefaultEnvironment>(cuenta);
+      c.pagar_mi_grupo().unwrap();
+      assert_eq!(c.pagos.get(1).unwrap().pagador, Some(cuenta));
+      assert_eq!(c.pagos.get(2).unwrap().pagador, Some(cuenta));
+      assert_eq!(c.tesoreria, 7000);
+    }
+    #[ink::test]
+    fn quitar_integrante_y_disolver_grupo_test(){
+      let mut c = Club::new();
+      c.registrar_socio(111, "A".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(222, "C".to_string(), "".to_string()).unwrap();
+      c.registrar_socio(333, "C".to_string(), "".to_string()).unwrap();
+      c.crear_grupo(111, 10).unwrap();
+      c.agregar_integrante(1, 222).unwrap();
+      c.agregar_integrante(1, 333).unwrap();
+      assert_eq!(c.quitar_integrante(1, 111), Err(ClubError::EsTitular));
+      assert_eq!(c.quitar_integrante(1, 444), Err(ClubError::NoIntegrante));
+      c.quitar_integrante(1, 222).unwrap();
+      //el integrante que se da de baja deja el grupo
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+      c.dar_de_baja(333).unwrap();
+      assert_eq!(c.get_grupos(), [(1, [111].to_vec())].to_vec());
+      assert_eq!(c.set_descuento_grupo(1, 150), Err(ClubError::DescuentoInvalido));
+      c.set_descuento_grupo(1, 20).unwrap();
+      assert_eq!(c.get_grupo(1).unwrap().descuento, 20);
+      c.disolver_grupo(1).unwrap();
+      assert!(c.get_grupos().is_empty());
+      assert!(!c.get_grupo(1).unwrap().activo);
+      assert_eq!(c.disolver_grupo(1), Err(ClubError::GrupoNoEncontrado));
+      //si el titular se da de baja el grupo se disuelve
+      assert_eq!(c.crear_grupo(111, 10), Ok(2));
+      c.agregar_integrante(2, 222).unwrap();
+      assert_eq!(c.agregar_integrante(2, 333), Err(ClubError::SocioInactivo));
+      ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(5000);
+      c.dar_de_baja(111).unwrap();
+      assert!(c.get_grupos().is_empty());
+      assert_eq!(c.crear_grupo(222, 10), Ok(3));
+    }
+  }
+}
@@ -4,8 +4,12 @@
 #[ink::contract]
 mod reporte {
     use club::ClubRef;
+    use club::ClubError;
+    use club::EstadoSocio;
+    use club::ResumenPago;
     use ink::prelude::vec::Vec;
     use ink::prelude::string::String;
+    #[cfg(test)]
     use ink_prelude::string::ToString;
   	
 
@@ -27,54 +31,61 @@ mod reporte {
         anio:u64,
     }
   	///El struct Socio es para mockear los socios y realizar los tests
+    #[cfg(test)]
     struct Socio {
-      dni: u128,
+      id: u128,
       categoria: String,
-      actividad: String,
+      actividades: Vec<String>,
+      estado: EstadoSocio,
   	}
   	///El struct Pago es para mockear los pagos y realizar los tests
+    #[cfg(test)]
   	struct Pago {
-      dni_socio:u128,
+      id_socio:u128,
       id: u128,
       fecha: u64,
       pagado: bool,
       costo: u128,
+      recargo: u128,
   	}
   	///El struct Info es para mockear el club y realizar los tests, este va a ser el que provee la informacion
+    #[cfg(test)]
   	struct Info{
-      #[cfg(test)]
       socios:Vec<Socio>,
-      #[cfg(test)]
       pagos:Vec<Pago>
       
   	}
+    #[cfg(test)]
   	impl Socio{
-        ///Crea un socio y lo retorna,recibe el dni, la categoria y la actividad del socio
+        ///Crea un socio y lo retorna,recibe el id, la categoria, las actividades y el estado del socio
         /// Ejemplo
         /// '''
-        ///     self.crear_socio(44851840,"A".to_string(),"TODO".to_string())
+        ///     self.crear_socio(44851840,"A".to_string(),["TODOS".to_string()].to_vec(),EstadoSocio::Activo)
         /// '''
-        fn crear_socio(dni: u128, categoria: String, actividad: String)->Self{
+        fn crear_socio(id_socio: u128, categoria: String, actividades: Vec<String>, estado: EstadoSocio)->Self{
             Self{
-            dni,
+            id: id_socio,
             categoria,
-            actividad,
+            actividades,
+            estado,
             }
         }
     }
+    #[cfg(test)]
     impl Pago{
-        ///Crea un pago y lo retorna,recibe el dni, el id,la fecha en la que se esta realizado, si fue pagado o no y el costo
+        ///Crea un pago y lo retorna,recibe el id del socio, el id,la fecha en la que se esta realizado, si fue pagado o no, el costo y el recargo por atraso
         /// Ejemplo
         ///'''
-        ///     self.crear_pago(44851840,2,227788993900,true,5000)
+        ///     self.crear_pago(44851840,2,227788993900,true,5000,0)
         ///'''
-        fn crear_pago(dni_socio:u128, id: u128,fecha: u64,pagado: bool,costo: u128)->Self{
+        fn crear_pago(id_socio:u128, id: u128,fecha: u64,pagado: bool,costo: u128,recargo: u128)->Self{
             Self{
-                dni_socio,
+                id_socio,
                 id,
                 fecha,
                 pagado,
                 costo,
+                recargo,
             }
         }
     }
@@ -94,20 +105,16 @@ mod reporte {
         pub fn new()->Self{
             Self { }
         }
-        ///Recibe del contrato Club el listado de los socios y los devuelve
+        ///Recibe del contrato Club el listado de los socios con su estado y los devuelve
         #[cfg(not(test))]
-        fn get_socios(&self)->Vec<u128>{
+        fn get_socios(&self)->Vec<(u128, EstadoSocio)>{
             self.club.get_socios()
         }
         ///Recibe del contrato Club el listado de los pagos de un socio en especifico y los devuelve
+        ///Si el Club no encuentra el socio o no deja consultarlo, devuelve el error del Club
         #[cfg(not(test))]
-        fn get_pagos(&self, dni: u128) -> Vec<(u128, u64 ,bool, u128)> {
-           let v= self.club.get_pago(dni);
-           if v.len() == 0{
-            panic!("no existe el socio");
-           }else{
-            return v;
-           }
+        fn get_pagos(&self, id_socio: u128) -> Result<Vec<ResumenPago>, ClubError> {
+           self.club.get_pago(id_socio)
         }
         ///Nos devuelve la fecha de hoy 
         #[cfg(not(test))]
@@ -121,10 +128,32 @@ mod reporte {
             1689711702000
         }
         ///Recibe del contrato Club la informacion de un socio en especifico y la devuelve
+        ///Si no hay un socio en esa posicion o el Club no deja consultarlo, devuelve el error del Club
         #[cfg(not(test))]
-        fn get_info_socio(&self, pos:u128)->(String,String){
+        fn get_info_socio(&self, pos:u128)->Result<(String,Vec<String>), ClubError>{
             self.club.get_info_socio(pos)
         }
+        ///Recibe del contrato Club el listado de los grupos familiares activos con sus socios y los devuelve
+        #[cfg(not(test))]
+        fn get_grupos(&self)->Vec<(u32, Vec<u128>)>{
+            self.club.get_grupos()
+        }
+        ///Recibe del contrato Club lo que debe cada socio de un grupo familiar, con los recargos y descontando los pagos parciales, y lo devuelve
+        ///Si el grupo no existe o el Club no deja consultarlo, devuelve el error del Club
+        #[cfg(not(test))]
+        fn get_deuda_grupo(&self, id: u32)->Result<Vec<(u128, u128)>, ClubError>{
+            self.club.get_deuda_grupo(id)
+        }
+        ///Recibe del contrato Club los nombres de las categorias del catalogo y los devuelve
+        #[cfg(not(test))]
+        fn get_nombres_categorias(&self)->Vec<String>{
+            self.club.get_nombres_categorias()
+        }
+        ///Recibe del contrato Club los nombres de las actividades registradas y los devuelve
+        #[cfg(not(test))]
+        fn get_nombres_actividades(&self)->Vec<String>{
+            self.club.get_nombres_actividades()
+        }
         ///Carga la informacion necesaria para mockear el club y poder probar los test 
         #[cfg(test)]
         fn crear_info(&self)-> Info{
@@ -134,140 +163,162 @@ mod reporte {
               socios,
               pagos,
             };
-            i.socios.push(Socio::crear_socio(44851840, "A".to_string(), "TODOS".to_string()));
-            i.socios.push(Socio::crear_socio(44851841, "B".to_string(), "FUTBOL".to_string()));
-            i.socios.push(Socio::crear_socio(44851842, "C".to_string(), "NADA".to_string()));
-            i.socios.push(Socio::crear_socio(44851843, "A".to_string(), "TODOS".to_string()));
-            i.socios.push(Socio::crear_socio(44851844, "B".to_string(), "TENIS".to_string()));
-            i.socios.push(Socio::crear_socio(44851845, "C".to_string(), "NADA".to_string()));
+            i.socios.push(Socio::crear_socio(44851840, "A".to_string(), ["TODOS".to_string()].to_vec(), EstadoSocio::Activo));
+            i.socios.push(Socio::crear_socio(44851841, "B".to_string(), ["FUTBOL".to_string(), "TENIS".to_string()].to_vec(), EstadoSocio::Activo));
+            i.socios.push(Socio::crear_socio(44851842, "C".to_string(), Vec::new(), EstadoSocio::Activo));
+            i.socios.push(Socio::crear_socio(44851843, "A".to_string(), ["TODOS".to_string()].to_vec(), EstadoSocio::Activo));
+            i.socios.push(Socio::crear_socio(44851844, "B".to_string(), ["TENIS".to_string()].to_vec(), EstadoSocio::Activo));
+            i.socios.push(Socio::crear_socio(44851845, "C".to_string(), Vec::new(), EstadoSocio::Activo));
+            i.socios.push(Socio::crear_socio(44851846, "B".to_string(), ["FUTBOL".to_string()].to_vec(), EstadoSocio::Suspendido));
+            i.socios.push(Socio::crear_socio(44851847, "A".to_string(), ["TODOS".to_string()].to_vec(), EstadoSocio::DadoDeBaja));
                 
-            i.pagos.push(Pago::crear_pago(44851840, 1, 1, true, 5000));
-            i.pagos.push(Pago::crear_pago(44851840, 2, self.tiempo(), true, 5000));
-            i.pagos.push(Pago::crear_pago(44851841, 3, self.tiempo(), true, 3000));
-            i.pagos.push(Pago::crear_pago(44851841, 4, self.tiempo(), true, 3000));
-            i.pagos.push(Pago::crear_pago(44851842, 5, self.tiempo(), true, 2000));
-            i.pagos.push(Pago::crear_pago(44851842, 6, self.tiempo(), true, 2000));
-            i.pagos.push(Pago::crear_pago(44851843, 7, 1, false, 5000));
-            i.pagos.push(Pago::crear_pago(44851843, 8, self.tiempo(), true, 5000));
-            i.pagos.push(Pago::crear_pago(44851844, 9, self.tiempo(), true, 3000));
-            i.pagos.push(Pago::crear_pago(44851844, 10, 1, false, 3000));
-            i.pagos.push(Pago::crear_pago(44851845, 11, self.tiempo(), true, 2000));
-            i.pagos.push(Pago::crear_pago(44851845, 12, 1, false, 2000));
-            return i;
-        }
-        /// metodo mockeado para el testing de socios,devuelve un listado de los dni de los socios
+            i.pagos.push(Pago::crear_pago(44851840, 1, 1, true, 5000, 0));
+            i.pagos.push(Pago::crear_pago(44851840, 2, self.tiempo(), true, 5000, 0));
+            i.pagos.push(Pago::crear_pago(44851841, 3, self.tiempo(), true, 3000, 0));
+            i.pagos.push(Pago::crear_pago(44851841, 4, self.tiempo(), true, 3000, 0));
+            i.pagos.push(Pago::crear_pago(44851842, 5, self.tiempo(), true, 2000, 0));
+            i.pagos.push(Pago::crear_pago(44851842, 6, self.tiempo(), true, 2000, 0));
+            i.pagos.push(Pago::crear_pago(44851843, 7, 1, false, 5000, 0));
+            i.pagos.push(Pago::crear_pago(44851843, 8, self.tiempo(), true, 5000, 500));
+            i.pagos.push(Pago::crear_pago(44851844, 9, self.tiempo(), true, 3000, 0));
+            i.pagos.push(Pago::crear_pago(44851844, 10, 1, false, 3000, 0));
+            i.pagos.push(Pago::crear_pago(44851845, 11, self.tiempo(), true, 2000, 0));
+            i.pagos.push(Pago::crear_pago(44851845, 12, 1, false, 2000, 0));
+            i.pagos.push(Pago::crear_pago(44851846, 13, 1, false, 3000, 0));
+            i.pagos.push(Pago::crear_pago(44851847, 14, 1, true, 5000, 0));
+            i
+        }
+        /// metodo mockeado para el testing de socios,devuelve un listado de los ids de los socios con su estado
         #[cfg(test)]
-        fn get_socios(&self)->Vec<u128>{
+        fn get_socios(&self)->Vec<(u128, EstadoSocio)>{
             let info = self.crear_info();
-            let mut vec: Vec<u128>=Vec::new();
+            let mut vec: Vec<(u128, EstadoSocio)>=Vec::new();
             for i in info.socios{
-                vec.push(i.dni);
+                vec.push((i.id, i.estado));
             }
             vec
         }
 
         ///metodo mockeado para el testing de pagos, devuelve un listado de los pagos de un socio en especifico
+        ///Si el socio no existe devuelve el error SocioNoEncontrado, como el Club
         #[cfg(test)]
-        fn get_pagos(&self,dni: u128)->Vec<(u128, u64 ,bool, u128)>{
+        fn get_pagos(&self,id_socio: u128)->Result<Vec<ResumenPago>, ClubError>{
             let info = self.crear_info();
-          	let vec = info.pagos
+            if !info.socios.iter().any(|socio| socio.id == id_socio){
+                return Err(ClubError::SocioNoEncontrado);
+            }
+          	Ok(info.pagos
             .iter()
-            .filter(|pago| pago.dni_socio == dni)
-            .map(|pago| (pago.id, pago.fecha, pago.pagado, pago.costo))
-            .collect();
-          	vec
+            .filter(|pago| pago.id_socio == id_socio)
+            .map(|pago| (pago.id, pago.fecha, pago.pagado, pago.costo, pago.recargo))
+            .collect())
+        }
+        ///Metodo mockeado para el testing, devuelve los grupos familiares con sus socios, el titular primero
+        #[cfg(test)]
+        fn get_grupos(&self)->Vec<(u32, Vec<u128>)>{
+            [(1, [44851840, 44851841].to_vec()), (2, [44851843, 44851844, 44851845].to_vec())].to_vec()
+        }
+        ///Metodo mockeado para el testing, devuelve lo que debe cada socio de un grupo familiar
+        ///El socio 44851843 debe el pago 7 con 250 de recargo, y el 44851844 ya pago 1500 del pago 10
+        ///Si el grupo no existe devuelve el error GrupoNoEncontrado, como el Club
+        #[cfg(test)]
+        fn get_deuda_grupo(&self, id: u32)->Result<Vec<(u128, u128)>, ClubError>{
+            match id{
+                1=>Ok([(44851840, 0), (44851841, 0)].to_vec()),
+                2=>Ok([(44851843, 5250), (44851844, 1500), (44851845, 2000)].to_vec()),
+                _=>Err(ClubError::GrupoNoEncontrado),
+            }
+        }
+        ///Metodo mockeado para el testing, devuelve los nombres de las categorias del catalogo
+        #[cfg(test)]
+        fn get_nombres_categorias(&self)->Vec<String>{
+            ["A".to_string(), "B".to_string(), "C".to_string()].to_vec()
+        }
+        ///Metodo mockeado para el testing, devuelve los nombres de las actividades registradas
+        #[cfg(test)]
+        fn get_nombres_actividades(&self)->Vec<String>{
+            ["FUTBOL", "BASQUET", "RUGBY", "HOCKEY", "NATACION", "TENIS", "PADDLE"].iter().map(|a| a.to_string()).collect()
         }
         ///Metodo mockeado para el testing, devuelva la categoria y la actividad de un socio en especifico
+        ///Si no hay un socio en esa posicion devuelve el error PosicionInvalida, como el Club
       	#[cfg(test)]
-      	fn get_info_socio(&self, i: u128)->(String, String){
+      	fn get_info_socio(&self, i: u128)->Result<(String, Vec<String>), ClubError>{
           let info = self.crear_info();
-          let tupla = (info.socios[i as usize].categoria.clone(), info.socios[i as usize].actividad.clone());
-          tupla
-        }
-        ///Crea un vector con los DNIs de los socios morosos y los retorna
-        ///Obtiene un vector con los DNIs de los socios, y si es moroso los agrega al vector
+          match info.socios.get(i as usize){
+            Some(socio)=>Ok((socio.categoria.clone(), socio.actividades.clone())),
+            None=>Err(ClubError::PosicionInvalida),
+          }
+        }
+        ///Crea un vector con los ids de los socios morosos y los retorna
+        ///Obtiene un vector con los ids de los socios, y si es moroso los agrega al vector
+        ///Los ids son el hash del dni de cada socio que calcula el Club, asi el reporte no expone los dni
+        ///Los socios dados de baja no se tienen en cuenta, los suspendidos si porque pueden seguir debiendo
         ///Si ninguno es moroso devuelve un vector vacio
+        ///Si el Club no deja consultar los pagos de un socio, devuelve ese error
         #[ink(message)]
-        pub fn get_pagos_pendientes(&self)->Vec<u128>{
+        pub fn get_pagos_pendientes(&self)->Result<Vec<u128>, ClubError>{
             let mut vector:Vec<u128> = Vec::new();
             let socios = self.get_socios();
             let fecha_hoy:u64 = self.tiempo();
-            for socio in socios{
-                if self.es_moroso(socio,fecha_hoy){
+            for (socio, estado) in socios{
+                if estado != EstadoSocio::DadoDeBaja && self.es_moroso(socio,fecha_hoy)?{
                     vector.push(socio);
                 }
             }
-            vector
+            Ok(vector)
         }
 
         /// es moroso devulve un booleano, indicando si el socio es moroso o no lo es
         ///llama a la funcion 'get_pago' que devuelve un vector de tuplas con informacion de los pagos de un socio en especifico
         ///con esa informacion se fija si pago o no pago, y si no pago, se fija si se paso de la fecha de vencimiento indicando que es moroso
-        
-        fn es_moroso(&self, socio: u128,fecha_hoy:u64)->bool{
-            let pagos = self.get_pagos(socio);
+        ///Si el Club no devuelve los pagos del socio, devuelve ese error
+        fn es_moroso(&self, socio: u128,fecha_hoy:u64)->Result<bool, ClubError>{
+            let pagos = self.get_pagos(socio)?;
             let mut ok = false;
             let mut i = 0;
             while i < pagos.len() && !ok{
-                if !pagos[i].2{ 
-                    if fecha_hoy > pagos[i].1{
-                        ok = true;
-                    }
+                if !pagos[i].2 && fecha_hoy > pagos[i].1{
+                    ok = true;
                 }
                 i+=1;
 
             }
-            return ok;
+            Ok(ok)
         }
         ///Nos devuelve un vector con los socios no morosos que tienen permitido asistir a una actividad deportiva especifica
-        /// La actividad la recibe como parametro, llama a la funcion 'get_socios' para obtener un listado de los dni de los socios
+        /// La actividad la recibe como parametro, llama a la funcion 'get_socios' para obtener un listado de los ids de los socios
         /// Llama a otra funcion para obtener la informacion de la categoria y actividad de ese socio en especifico 
-        /// Si no es moroso ese socio, nos fijamos su categoria
-        /// 	Si tiene categoria 'A', como tiene permitido todas las actividades, lo agrego al listado
-        ///		Si es categoria 'B', me fijo si la actividad es igual a la que le pasaron por parametro y si es igual, se agrega
-        ///		Si es categoria 'C', como no tiene permitido ninguna actividad extra, no se incluye directamente
-        ///Si es moroso, no se agrega al listado.
+        /// Si no es moroso ese socio, nos fijamos en el listado de sus actividades, que depende de lo que incluye su categoria en el catalogo
+        /// 	Si tiene 'TODOS', como su categoria le permite todas las actividades, lo agrego al listado
+        ///		Si no, me fijo si alguna de sus actividades es igual a la que le pasaron por parametro y si es igual, se agrega
+        ///		Si no tiene ninguna actividad, no coincide con ninguna y no se incluye
+        ///Si es moroso, o si esta suspendido o dado de baja, no se agrega al listado.
       	///Si ninguno cumple los requerimientos, devuelve un vector vacio
+        ///Si la actividad no esta registrada en el club, devuelve el error ActividadInvalida, y si el Club no deja consultar un socio devuelve ese error
         #[ink(message)]
-        pub fn get_socios_no_morosos_actividad_especifica(&self, actividad: String)->Vec<u128>{
+        pub fn get_socios_no_morosos_actividad_especifica(&self, actividad: String)->Result<Vec<u128>, ClubError>{
             let mut vector: Vec<u128> = Vec::new();
-            if self.es_actividad_valida(&actividad){ 
-                let socios = self.get_socios();
-                let fecha_hoy = self.tiempo();
-                for i in 0..socios.len(){
-                    let info = self.get_info_socio(i as u128); //falta ver esto
-                    if !self.es_moroso(socios[i], fecha_hoy) {
-                        if info.0 == "A"{
-                            vector.push(socios[i]);
-                        }else{
-                            if info.0 == "B"{
-                                if info.1 == actividad{
-                                    vector.push(socios[i]);
-                                }
-                            }
-                        }
-                    }
-                } 
+            if !self.es_actividad_valida(&actividad){
+                return Err(ClubError::ActividadInvalida);
             }
-            return vector;
-        }
-        ///Recibe un actividad y retorna true si es una actividad del club, false en caso contrario
-        fn es_actividad_valida(&self,actividad: &String)->bool{
-            let act = match actividad as &str{
-                "FUTBOL"=>true, 
-                "BASQUET"=>true, 
-                "RUGBY"=>true,
-                "HOCKEY"=>true,
-                "NATACION"=>true, 
-                "TENIS"=>true,
-                "PADDLE"=>true,
-                _=>panic!("actividad incorrecta"),
-            };
-            act
+            let socios = self.get_socios();
+            let fecha_hoy = self.tiempo();
+            for (i, &(id_socio, estado)) in socios.iter().enumerate(){
+                if estado == EstadoSocio::Activo && !self.es_moroso(id_socio, fecha_hoy)? {
+                    let info = self.get_info_socio(i as u128)?;
+                    if info.1.iter().any(|a| a == "TODOS" || *a == actividad){
+                        vector.push(id_socio);
+                    }
+                }
+            } 
+            Ok(vector)
+        }
+        ///Recibe un actividad y retorna true si es una actividad registrada en el club, false en caso contrario
+        fn es_actividad_valida(&self,actividad: &str)->bool{
+            self.get_nombres_actividades().iter().any(|a| a == actividad)
         }
         /// calcular_fecha recibe una fecha (timestamp) y lo devuelve en formato fecha
         /// Sacamos la cantidad de dias que le tenemos que sumar y se la pasamos a la funcion 'sumar_dias' para que nos devuelva la fecha sumada con esa cantidad de dias y la retornamos
-    
         fn calcular_fecha(&self, time:u64)->Fecha{
             let dia = 1;
             let mes = 1;
@@ -287,35 +338,57 @@ mod reporte {
                 }
             
             }
-            return f;
+            f
         }
-        ///Recibe un mes y un año y retorna un listado con la recaudacion de cada categoria de ese mes y año    
+        ///Recibe un mes y un año y retorna un listado con la recaudacion de cada categoria del catalogo del club de ese mes y año    
+        ///Si el Club no deja consultar los pagos o la informacion de un socio, devuelve ese error
         #[ink(message)]
-        pub fn recaudacion_mensual(&self, mes: u64, anio: u64)->Vec<(String, u128)>{
-            let mut map:Vec<(String,u128)> = Vec::new();
-            let mut a = 0;
-            let mut b = 0;
-            let mut c = 0;
+        pub fn recaudacion_mensual(&self, mes: u64, anio: u64)->Result<Vec<(String, u128)>, ClubError>{
+            let mut map:Vec<(String,u128)> = self.get_nombres_categorias().into_iter().map(|nombre| (nombre, 0)).collect();
             let socios = self.get_socios();
-            for i in 0..socios.len(){
-                let pagos = self.get_pagos(socios[i]);
-                let info = self.get_info_socio(i as u128); //falta esto
-                for j in 0..pagos.len(){
-                    let fecha = self.calcular_fecha(pagos[j].1);
+            for (i, socio) in socios.iter().enumerate(){
+                let pagos = self.get_pagos(socio.0)?;
+                let info = self.get_info_socio(i as u128)?;
+                for pago in &pagos{
+                    let fecha = self.calcular_fecha(pago.1);
                     if fecha.mes == mes && fecha.anio == anio{
-                        match &info.0 as &str {
-                            "A"=> a+=pagos[j].3,
-                            "B"=> b+=pagos[j].3,
-                            "C"=> c+=pagos[j].3,
-                            _=>(),
+                        if let Some(total) = map.iter_mut().find(|(nombre, _)| *nombre == info.0){
+                            total.1 += pago.3;
                         }
                     }
                 }
             }
-            map.push(("A".to_string(), a));
-            map.push(("B".to_string(), b));
-            map.push(("C".to_string(), c));
-            return map;
+            Ok(map)
+        }
+        ///Retorna un listado con la deuda de cada grupo familiar del club, con el id del grupo
+        ///La deuda de un grupo es la suma de lo que el Club calcula que debe cada uno de sus socios: lo que les falta pagar
+        ///de sus pagos pendientes, esten vencidos o no, con los recargos por atraso y descontando los pagos parciales
+        ///Si el club no tiene grupos devuelve un vector vacio
+        ///Si el Club no deja consultar la deuda de un grupo, devuelve ese error
+        #[ink(message)]
+        pub fn deuda_por_grupo(&self)->Result<Vec<(u32, u128)>, ClubError>{
+            let mut vector: Vec<(u32, u128)> = Vec::new();
+            for (id, _) in self.get_grupos(){
+                let deuda = self.get_deuda_grupo(id)?.iter().map(|(_, deuda)| deuda).sum();
+                vector.push((id, deuda));
+            }
+            Ok(vector)
+        }
+        ///Recibe un mes y un año y retorna el total cobrado en recargos por atraso en ese mes y año
+        ///Los recargos no se suman en recaudacion_mensual, que solo cuenta las cuotas de cada categoria
+        ///Si el Club no deja consultar los pagos de un socio, devuelve ese error
+        #[ink(message)]
+        pub fn recargos_mensuales(&self, mes: u64, anio: u64)->Result<u128, ClubError>{
+            let mut total = 0;
+            for (id_socio, _) in self.get_socios(){
+                for pago in self.get_pagos(id_socio)?{
+                    let fecha = self.calcular_fecha(pago.1);
+                    if fecha.mes == mes && fecha.anio == anio{
+                        total += pago.4;
+                    }
+                }
+            }
+            Ok(total)
         }
     }
     impl Fecha{
@@ -331,7 +404,7 @@ mod reporte {
             } else {
                 ok = false;
             }
-            return ok;
+            ok
         }
         ///A la fecha que esta guardada, le suma la cantidad de dias pasado por parametro
         fn sumar_dias(&mut self,mut cant:u64){
@@ -347,7 +420,7 @@ mod reporte {
                     self.dia += cant;
                     cant -= cant;
                 } else {
-                    cant = cant - (d - self.dia);
+                    cant -= d - self.dia;
                     self.dia = 0;
                     self.mes += 1;
                     if self.mes == 13{
@@ -367,44 +440,50 @@ mod reporte {
         fn crear_info_test() {
           let reporte = Reporte::new();
           let info = reporte.crear_info();
-          assert_eq!(info.socios.len(), 6);
-          assert_eq!(info.pagos.len(), 12);
+          assert_eq!(info.socios.len(), 8);
+          assert_eq!(info.pagos.len(), 14);
         }
     	#[ink::test]
         fn get_socios_test() {
           let reporte = Reporte::new();
           let socios = reporte.get_socios();
-          assert_eq!(socios.len(), 6);
-          assert_eq!(socios[0], 44851840);
+          assert_eq!(socios.len(), 8);
+          assert_eq!(socios[0], (44851840, EstadoSocio::Activo));
+          assert_eq!(socios[6], (44851846, EstadoSocio::Suspendido));
         }
         #[ink::test]
     	fn get_pagos_test() {
           let reporte = Reporte::new();
-          let pagos = reporte.get_pagos(44851840);
+          let pagos = reporte.get_pagos(44851840).unwrap();
           assert_eq!(pagos.len(), 2);
           assert_eq!(pagos[0].1, 1);
           assert!(pagos[0].2);
+          assert_eq!(reporte.get_pagos(1), Err(ClubError::SocioNoEncontrado));
         }
         #[ink::test]
     	fn get_info_socio_test() {
           let reporte = Reporte::new();
-          let info = reporte.get_info_socio(0);
+          let info = reporte.get_info_socio(0).unwrap();
           assert_eq!(info.0, "A");
-          assert_eq!(info.1, "TODOS");
+          assert_eq!(info.1, ["TODOS".to_string()].to_vec());
+          assert_eq!(reporte.get_info_socio(8), Err(ClubError::PosicionInvalida));
         }
     	#[ink::test]
         fn es_moroso_test(){
           let reporte = Reporte::new();
-          let ok1 = reporte.es_moroso(44851840, 12345678);
-          let ok2 = reporte.es_moroso(44851845, 12345678);
+          let ok1 = reporte.es_moroso(44851840, 12345678).unwrap();
+          let ok2 = reporte.es_moroso(44851845, 12345678).unwrap();
           assert!(!ok1);
           assert!(ok2);
+          assert_eq!(reporte.es_moroso(1, 12345678), Err(ClubError::SocioNoEncontrado));
         }
         #[ink::test]
         fn get_pagos_pendientes_test(){
           let reporte = Reporte::new();
-          let vector = reporte.get_pagos_pendientes();
-          assert_eq!(vector.len(), 3);
+          let vector = reporte.get_pagos_pendientes().unwrap();
+          //el socio suspendido 44851846 tambien debe su pago
+          assert_eq!(vector.len(), 4);
+          assert!(vector.contains(&44851846));
         }
         #[ink::test]
         fn calcular_fecha_test(){
@@ -454,7 +533,7 @@ mod reporte {
         #[ink::test]
         fn recaudacion_mensual_test(){
             let r = Reporte::new();
-            let vec=r.recaudacion_mensual(7,2023);
+            let vec=r.recaudacion_mensual(7,2023).unwrap();
             assert_eq!(vec[0].0,"A");
             assert_eq!(vec[0].1,10000);
           	assert_eq!(vec[1].0,"B");
@@ -463,25 +542,59 @@ mod reporte {
             assert_eq!(vec[2].1,6000);
         }
         
+        #[ink::test]
+        fn recargos_mensuales_test(){
+            let r = Reporte::new();
+            assert_eq!(r.recargos_mensuales(7,2023), Ok(500));
+            assert_eq!(r.recargos_mensuales(8,2023), Ok(0));
+            //el recargo no cambia lo recaudado por la categoria A
+            assert_eq!(r.recaudacion_mensual(7,2023).unwrap()[0].1, 10000);
+        }
+        #[ink::test]
+        fn deuda_por_grupo_test(){
+            let r = Reporte::new();
+            //el grupo 2 debe los pagos 7 con recargo, lo que falta del 10 y el 12
+            assert_eq!(r.deuda_por_grupo(), Ok([(1, 0), (2, 8750)].to_vec()));
+            assert_eq!(r.get_deuda_grupo(3), Err(ClubError::GrupoNoEncontrado));
+        }
         #[ink::test]
         fn get_socios_no_morosos_actividad_especifica_test(){
             let r = Reporte::new();
-            let v = r.get_socios_no_morosos_actividad_especifica("FUTBOL".to_string());
+            let v = r.get_socios_no_morosos_actividad_especifica("FUTBOL".to_string()).unwrap();
             assert_eq!(v[0], 44851840);
             assert_eq!(v[1], 44851841);
   	    }
         #[ink::test]
-        #[should_panic(expected = "actividad incorrecta")]
+        fn get_socios_no_morosos_varias_actividades_test(){
+            let r = Reporte::new();
+            //44851841 hace FUTBOL y TENIS, 44851844 hace TENIS pero es moroso
+            let v = r.get_socios_no_morosos_actividad_especifica("TENIS".to_string()).unwrap();
+            assert_eq!(v, [44851840, 44851841].to_vec());
+        }
+        #[ink::test]
+        fn get_socios_no_morosos_solo_activos_test(){
+            let r = Reporte::new();
+            //44851847 tiene la categoria A pero esta dado de baja
+            let v = r.get_socios_no_morosos_actividad_especifica("HOCKEY".to_string()).unwrap();
+            assert_eq!(v, [44851840].to_vec());
+        }
+        #[ink::test]
+        fn get_socios_no_morosos_actividad_invalida_test(){
+            let r = Reporte::new();
+            let v = r.get_socios_no_morosos_actividad_especifica("ATLETISMO".to_string());
+            assert_eq!(v, Err(ClubError::ActividadInvalida));
+        }
+        #[ink::test]
           fn es_actividad_valida_test(){
                 let r = Reporte::new();
-                assert!(r.es_actividad_valida(&"FUTBOL".to_string()));
-                assert!(r.es_actividad_valida(&"BASQUET".to_string()));
-                assert!(r.es_actividad_valida(&"HOCKEY".to_string()));
-                assert!(r.es_actividad_valida(&"PADDLE".to_string()));
-                assert!(r.es_actividad_valida(&"TENIS".to_string()));
-                assert!(r.es_actividad_valida(&"RUGBY".to_string()));
-                assert!(r.es_actividad_valida(&"NATACION".to_string()));
-                assert!(!r.es_actividad_valida(&"ATLETISMO".to_string()));
+                assert!(r.es_actividad_valida("FUTBOL"));
+                assert!(r.es_actividad_valida("BASQUET"));
+                assert!(r.es_actividad_valida("HOCKEY"));
+                assert!(r.es_actividad_valida("PADDLE"));
+                assert!(r.es_actividad_valida("TENIS"));
+                assert!(r.es_actividad_valida("RUGBY"));
+                assert!(r.es_actividad_valida("NATACION"));
+                assert!(!r.es_actividad_valida("ATLETISMO"));
             }
     }
   